@@ -2,10 +2,14 @@ use bevy::prelude::*;
 
 use crate::asset::asset_loader::PluginAssetLoader;
 use crate::episode::episode::PluginEpsiode;
+use crate::episode::episode_definition::PluginEpsiodeDefinition;
+use crate::suite::suite::PluginSuite;
 use crate::level::level::PluginLevel;
+use crate::level::definition::level_definition::PluginLevelDefinition;
 use crate::config::PluginConfig;
 use crate::game::game::PluginGame;
 use crate::game::debug::PluginDebug;
+use crate::game::replay::Replay;
 use crate::editor::editor::PluginEditor;
 use crate::app::common::{
     PluginAppCommon,
@@ -17,7 +21,7 @@ use crate::app::common::{
 
 #[derive(Resource, Default)]
 pub struct ResTypeRegister {
-    type_register: AppTypeRegistry,
+    pub type_register: AppTypeRegistry,
 }
 
 pub fn configure_default(app: &mut App){
@@ -33,6 +37,7 @@ pub fn configure_default(app: &mut App){
         .add_plugins(PluginConfig)
         .add_plugins(PluginAssetLoader)
         .add_plugins(PluginEpsiode)
+        .add_plugins(PluginSuite)
         .add_plugins(PluginLevel)
         .add_plugins(PluginGame)
         .add_plugins(PluginAppCommon);
@@ -47,6 +52,15 @@ pub fn configure_dev(app: &mut App){
         .add_systems(PostStartup, s_fill_type_register);
 }
 
+// Types of level, episode and replay files, without any window nor render
+// plugin: enough to read and write them from tools and tests.
+pub fn get_headless_type_registry() -> AppTypeRegistry {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition));
+    app.register_type::<Replay>();
+    app.world().resource::<AppTypeRegistry>().clone()
+}
+
 fn s_fill_type_register(world: &mut World) {
     let res = world.resource::<AppTypeRegistry>().clone();
     let mut type_register = world.resource_mut::<ResTypeRegister>();
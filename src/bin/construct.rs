@@ -10,7 +10,7 @@ use hedgeclog::app::builder_bevy_app::{
 
 use hedgeclog::episode::edit_episode::{
     s_create_origin_level_on_curr_episode, 
-    s_create_new_episode
+    s_load_or_create_construct_episode
 };
 use hedgeclog::episode::load_run_episode::{
     s_run_only_episode,
@@ -30,7 +30,7 @@ fn main() {
         .add_systems(
             PostStartup,
             (
-                s_create_new_episode, 
+                s_load_or_create_construct_episode, 
                 s_run_only_episode,
                 s_create_origin_level_on_curr_episode,
                 s_run_origin_level_on_curr_episode
@@ -1,3 +1,4 @@
+// cargo run --bin game -- [--replay <file> [--speed <inputs per second>]]
 use bevy::prelude::*;
 use hedgeclog::app::builder_bevy_app::{
     configure_default,
@@ -5,11 +6,86 @@ use hedgeclog::app::builder_bevy_app::{
     AppType
 
 };
+use hedgeclog::episode::load_run_episode::{
+    s_run_only_episode,
+    s_run_origin_level_on_curr_episode
+};
+use hedgeclog::game::replay::{
+    load_replay_from_file,
+    s_run_replay_level,
+    ResReplayPlayback,
+    ResReplayRecording,
+    REPLAY_DEFAULT_DIRECTORY,
+    REPLAY_DEFAULT_INPUTS_PER_SECOND,
+};
+use hedgeclog::suite::load_run_suite::{s_load_only_suite, s_run_only_suite};
+use hedgeclog::suite::save_game::{ResSaveGame, SAVE_GAME_DEFAULT_PATH};
+use hedgeclog::suite::suite_definition::{
+    get_suite_file_path,
+    ResSuiteToRun,
+    SUITE_DEFAULT_FILENAME
+};
 
 
 fn main() {
     let mut app = App::new();
     configure_default(&mut app);
     set_episode_location(&mut app, AppType::Production);
+    // A replay played back is not recorded again.
+    match get_replay_playback(&app) {
+        Some(replay_playback) => {
+            app.insert_resource(replay_playback);
+        },
+        None => {
+            app.insert_resource(ResReplayRecording{
+                directory: Some(REPLAY_DEFAULT_DIRECTORY.to_string()),
+                ..Default::default()
+            });
+        },
+    }
+    app
+        .insert_resource(ResSaveGame{
+            file_path: Some(SAVE_GAME_DEFAULT_PATH.to_string()),
+            ..Default::default()
+        })
+        .insert_resource(ResSuiteToRun{
+            suite_path: Some(get_suite_file_path(SUITE_DEFAULT_FILENAME)),
+        })
+        .add_systems(
+            PostStartup,
+            (
+                s_load_only_suite,
+                s_run_only_suite,
+                s_run_only_episode,
+                s_run_origin_level_on_curr_episode,
+                s_run_replay_level,
+            ).chain()
+        );
     app.run();
 }
+
+// Replay asked on the command line, None if there is none or it can not be
+// read.
+fn get_replay_playback(app: &App) -> Option<ResReplayPlayback> {
+    let args: Vec<String> = std::env::args().collect();
+    let get_arg = |name: &str| {
+        args.iter().position(|arg| arg == name).and_then(|idx| args.get(idx + 1))
+    };
+    let file_path = get_arg("--replay")?;
+    let type_registry = app.world().resource::<AppTypeRegistry>();
+    let replay = match load_replay_from_file(file_path, type_registry) {
+        Ok(replay) => replay,
+        Err(error) => {
+            error!("Replay not played back: {error}");
+            return None
+        }
+    };
+    let inputs_per_second = get_arg("--speed")
+        .and_then(|speed| speed.parse().ok())
+        .unwrap_or(REPLAY_DEFAULT_INPUTS_PER_SECOND);
+    Some(ResReplayPlayback{
+        replay: Some(replay),
+        inputs_per_second,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,298 @@
+// cargo run --bin level_tool -- <command> <file>...
+// Works on level and episode files without any window nor render plugin, to
+// be run in CI and pre-commit hooks.
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+use hedgeclog::app::builder_bevy_app::get_headless_type_registry;
+use hedgeclog::episode::episode_definition::{
+    EpisodeDescription,
+    EpisodeGrid,
+    EpisodeInfo,
+    EpisodeUID,
+    DEFAULT_EPISODE_START_LOCATION,
+};
+use hedgeclog::episode::serialize_episode::{
+    deserialize_episode_description,
+    serialize_episode_description,
+};
+use hedgeclog::game::replay::{load_replay_from_file, verify_replay};
+use hedgeclog::level::actions::ascii_level::{level_description_from_ascii, level_description_to_ascii};
+use hedgeclog::level::actions::serialize::{
+    deserialize_level_description,
+    serialize_level_description,
+    write_file,
+};
+use hedgeclog::level::definition::level_definition::LevelDescription;
+use hedgeclog::puzzle::solver::{solve_level_description, EnumSolverResult, SolverBounds};
+use uuid::Uuid;
+
+const USAGE: &str = r#"usage: level_tool <command> ...
+
+  validate <file>...                     check files can be played
+  print <file>...                        print levels as ascii grids
+  solve <file>... [--max-moves N] [--max-states N]
+                                         print shortest winning moves
+  verify <replay> <file>...              check replay reaches its recorded
+                                         outcome on its level, found in files
+  convert <input> <output> [--to level|episode|ascii] [--level UUID]
+                                         write input in another format,
+                                         default format from output extension
+
+files: level or episode .ron files, ascii .txt levels."#;
+
+// -- FILES ------------------------------------------------------------------
+
+enum EnumLevelFile {
+    Level(LevelDescription),
+    Episode(Box<EpisodeDescription>),
+}
+
+impl EnumLevelFile {
+    fn get_levels(&self) -> &[LevelDescription] {
+        match self {
+            EnumLevelFile::Level(level_description) => std::slice::from_ref(level_description),
+            EnumLevelFile::Episode(episode_description) => &episode_description.levels,
+        }
+    }
+
+    fn get_errors(&self) -> Vec<String> {
+        match self {
+            EnumLevelFile::Level(level_description) => level_description.get_errors(),
+            EnumLevelFile::Episode(episode_description) => episode_description.get_errors(),
+        }
+    }
+}
+
+// An episode file is the one holding an EpisodeInfo, a level file would be
+// read as the first level of the episode.
+fn load_file(file_path: &str, type_registry: &AppTypeRegistry) -> Result<EnumLevelFile, String> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    let result = if file_path.ends_with(".txt") {
+        level_description_from_ascii(&content).map(EnumLevelFile::Level)
+    } else if is_episode_scene(&content, type_registry)? {
+        deserialize_episode_description(&content, type_registry)
+            .map(|episode_description| EnumLevelFile::Episode(Box::new(episode_description)))
+    } else {
+        deserialize_level_description(&content, type_registry).map(EnumLevelFile::Level)
+    };
+    result.map_err(|error| format!("{file_path}: {error}"))
+}
+
+// Told from the scene components rather than from the file text, which a
+// type rename or any matching string would fool.
+fn is_episode_scene(content: &str, type_registry: &AppTypeRegistry) -> Result<bool, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(content, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+    Ok(
+        scene.entities.iter()
+            .flat_map(|entity| entity.components.iter())
+            .any(|component| EpisodeInfo::from_reflect(component.as_ref()).is_some())
+    )
+}
+
+// Level given by uid, or the only level of the file.
+fn select_level<'a>(
+    levels: &'a [LevelDescription],
+    level_uid: Option<&str>,
+) -> Result<&'a LevelDescription, String> {
+    match level_uid {
+        Some(level_uid) => {
+            let level_uid = Uuid::parse_str(level_uid).map_err(|error| format!("--level: {error}"))?;
+            levels.iter()
+                .find(|level_description| level_description.uuid == level_uid)
+                .ok_or(format!("level {level_uid} not found"))
+        },
+        None => match levels {
+            [level_description] => Ok(level_description),
+            _ => Err(format!("{} levels, choose one with --level UUID", levels.len())),
+        },
+    }
+}
+
+// Positional arguments and "--name value" options.
+fn parse_args(args: &[String]) -> Result<(Vec<&str>, HashMap<&str, &str>), String> {
+    let mut positionals = Vec::new();
+    let mut options = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(name) => {
+                let value = args.next().ok_or(format!("--{name} has no value"))?;
+                options.insert(name, value.as_str());
+            },
+            None => positionals.push(arg.as_str()),
+        }
+    }
+    Ok((positionals, options))
+}
+
+fn parse_option_usize(options: &HashMap<&str, &str>, name: &str, default: usize) -> Result<usize, String> {
+    match options.get(name) {
+        Some(value) => value.parse().map_err(|error| format!("--{name}: {error}")),
+        None => Ok(default),
+    }
+}
+
+// -- COMMANDS ---------------------------------------------------------------
+
+fn validate(file_paths: &[&str], type_registry: &AppTypeRegistry) -> Result<(), String> {
+    let mut is_valid = true;
+    for file_path in file_paths {
+        let errors = match load_file(file_path, type_registry) {
+            Ok(level_file) => level_file.get_errors(),
+            Err(error) => vec![error],
+        };
+        if errors.is_empty() {
+            println!("{file_path}: ok");
+        }
+        for error in errors {
+            println!("{file_path}: {error}");
+            is_valid = false;
+        }
+    }
+    if is_valid { Ok(()) } else { Err("invalid files".to_string()) }
+}
+
+fn print(file_paths: &[&str], type_registry: &AppTypeRegistry) -> Result<(), String> {
+    for file_path in file_paths {
+        let level_file = load_file(file_path, type_registry)?;
+        for level_description in level_file.get_levels() {
+            println!("{}", level_description_to_ascii(level_description));
+        }
+    }
+    Ok(())
+}
+
+fn solve(
+    file_paths: &[&str],
+    options: &HashMap<&str, &str>,
+    type_registry: &AppTypeRegistry,
+) -> Result<(), String> {
+    let default_bounds = SolverBounds::default();
+    let bounds = SolverBounds {
+        max_move_count: parse_option_usize(options, "max-moves", default_bounds.max_move_count)?,
+        max_state_count: parse_option_usize(options, "max-states", default_bounds.max_state_count)?,
+    };
+    let mut is_all_solved = true;
+    for file_path in file_paths {
+        let level_file = load_file(file_path, type_registry)?;
+        for level_description in level_file.get_levels() {
+            let uuid = level_description.uuid;
+            match solve_level_description(level_description, &bounds) {
+                EnumSolverResult::Solved(moves) => {
+                    let moves: Vec<String> = moves.iter().map(|direction| format!("{direction:?}")).collect();
+                    println!("{file_path}: level {uuid}: {} moves: {}", moves.len(), moves.join(" "));
+                },
+                EnumSolverResult::NoSolution => {
+                    println!("{file_path}: level {uuid}: no solution");
+                    is_all_solved = false;
+                },
+                EnumSolverResult::NoSolutionWithinBound => {
+                    println!(
+                        "{file_path}: level {uuid}: no solution within {} moves, {} states",
+                        bounds.max_move_count,
+                        bounds.max_state_count,
+                    );
+                    is_all_solved = false;
+                },
+            }
+        }
+    }
+    if is_all_solved { Ok(()) } else { Err("unsolved levels".to_string()) }
+}
+
+fn verify(
+    replay_path: &str,
+    file_paths: &[&str],
+    type_registry: &AppTypeRegistry,
+) -> Result<(), String> {
+    let replay = load_replay_from_file(replay_path, type_registry)?;
+    for file_path in file_paths {
+        let level_file = load_file(file_path, type_registry)?;
+        let Some(level_description) = level_file.get_levels().iter()
+            .find(|level_description| level_description.uuid == replay.level_uid)
+        else {
+            continue
+        };
+        verify_replay(&replay, level_description).map_err(|error| format!("{replay_path}: {error}"))?;
+        println!("{replay_path}: ok, {:?} in {} inputs", replay.outcome, replay.inputs.len());
+        return Ok(())
+    }
+    Err(format!("{replay_path}: level {} not found", replay.level_uid))
+}
+
+// A level converted to an episode is put on its start location.
+fn convert(
+    input_path: &str,
+    output_path: &str,
+    options: &HashMap<&str, &str>,
+    type_registry: &AppTypeRegistry,
+) -> Result<(), String> {
+    let level_file = load_file(input_path, type_registry)?;
+    let default_format = if output_path.ends_with(".txt") { "ascii" } else { "level" };
+    let level_uid = options.get("level").copied();
+    let serialized = match options.get("to").copied().unwrap_or(default_format) {
+        "ascii" => level_description_to_ascii(select_level(level_file.get_levels(), level_uid)?),
+        "level" => serialize_level_description(select_level(level_file.get_levels(), level_uid)?, type_registry)?,
+        "episode" => {
+            let mut episode_description = match level_file {
+                EnumLevelFile::Episode(episode_description) => *episode_description,
+                EnumLevelFile::Level(level_description) => {
+                    let mut episode_grid = EpisodeGrid::default();
+                    let [x, y] = DEFAULT_EPISODE_START_LOCATION;
+                    episode_grid.episode_grid[x][y] = Some(level_description.uuid);
+                    EpisodeDescription {
+                        episode_info: EpisodeInfo::default(),
+                        episode_uid: EpisodeUID{uid: Uuid::new_v4()},
+                        episode_grid,
+                        levels: vec![level_description],
+                    }
+                },
+            };
+            episode_description.episode_info.level_path = output_path.to_string();
+            if episode_description.episode_info.level_name.is_empty() {
+                episode_description.episode_info.level_name = std::path::Path::new(output_path)
+                    .file_stem()
+                    .map_or(String::new(), |stem| stem.to_string_lossy().to_string());
+            }
+            serialize_episode_description(&episode_description, type_registry)?
+        },
+        format => return Err(format!("unknown format {format}")),
+    };
+    write_file(output_path, &serialized).map_err(|error| format!("{output_path}: {error}"))?;
+    println!("{input_path} -> {output_path}");
+    Ok(())
+}
+
+// -- MAIN -------------------------------------------------------------------
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let type_registry = get_headless_type_registry();
+    let result = parse_args(&args).and_then(|(positionals, options)| {
+        match positionals.as_slice() {
+            ["validate", file_paths @ ..] if !file_paths.is_empty() => validate(file_paths, &type_registry),
+            ["print", file_paths @ ..] if !file_paths.is_empty() => print(file_paths, &type_registry),
+            ["solve", file_paths @ ..] if !file_paths.is_empty() => solve(file_paths, &options, &type_registry),
+            ["verify", replay_path, file_paths @ ..] if !file_paths.is_empty() => {
+                verify(replay_path, file_paths, &type_registry)
+            },
+            ["convert", input_path, output_path] => convert(input_path, output_path, &options, &type_registry),
+            _ => Err(USAGE.to_string()),
+        }
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -27,6 +27,17 @@ pub enum StateLevelLoaded {
     Loaded,
 }
 
+// Outcome of the level being played.
+// Reset to "Playing" every time a level is loaded.
+// Listen to OnEnter(Won) / OnEnter(Lost) to react to the end of a level.
+#[derive(States, Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum StateLevelOutcome {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
 pub struct PluginConfig;
 
 impl Plugin for PluginConfig{
@@ -34,6 +45,7 @@ impl Plugin for PluginConfig{
         app
             .init_state::<StateGlobal>()
             .init_state::<StateLevelLoaded>()
+            .init_state::<StateLevelOutcome>()
             .init_state::<StateUserInputAllowed>();
     }
 }
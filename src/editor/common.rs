@@ -2,6 +2,8 @@ use std::usize;
 
 use bevy::prelude::*;
 
+use crate::editor::keymap::ResEditorKeymap;
+
 pub const TRANSLATION_EDITOR_TILE_SELECTOR_ORIGIN: Vec3 = Vec3::new(1000.0, 0.0, 1000.0);
 
 #[derive(States, Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -22,7 +24,15 @@ pub enum StateEditorMode {
     Normal,
     Tile,
     Hedgehog,
-    // Test,
+    Try,
+}
+
+// Asked by "q": edits are kept, saved or discarded before leaving editor.
+#[derive(States, Default, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum StateEditorLeavePrompt {
+    #[default]
+    Hidden,
+    Shown,
 }
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -70,6 +80,8 @@ impl Plugin for PluginEditorData {
             .init_state::<StateEditorLoaded>()
             .init_state::<StateEditorView>()
             .init_state::<StateEditorMode>()
+            .init_state::<StateEditorLeavePrompt>()
+            .init_resource::<ResEditorKeymap>()
             .add_event::<EventCursorGridPositionChanged>()
             .add_event::<EventEditorSubSystemLoaded>()
             .add_event::<EventEditorSubSystemSetup>()
@@ -1,7 +1,7 @@
 use std::{usize, cmp};
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use crate::level::definition::level_definition::LEVEL_ORIGIN; 
+use crate::level::definition::level_definition::{LEVEL_ORIGIN, ResCurrentLevelGrid};
 use crate::editor::common::{
     EventCursorGridPositionChanged,
     EventEditorSubSystemLoaded,
@@ -125,6 +125,7 @@ fn s_update_cursor_to_world(
 fn s_update_cursor_to_grid_position(
     mut r_cursor_grid_position: ResMut<CursorGridPosition>,
     r_cursor_to_ground_coordonate: Res<CursorToGroundCoordonate>,
+    r_grid: Res<ResCurrentLevelGrid>,
     mut e_cursor_grid_position_changed: EventWriter<EventCursorGridPositionChanged>,
 ) {
     let previous_grid_pos_x = r_cursor_grid_position.grid_pos_x;
@@ -132,12 +133,10 @@ fn s_update_cursor_to_grid_position(
 
     let local_position = r_cursor_to_ground_coordonate.global - LEVEL_ORIGIN;
 
-    const LEVEL_SIZE:usize = 8;
-
     let grid_pos_x = cmp::max(
         cmp::min(
             ((local_position.x + (TILE_SIZE / 2.0)) / TILE_SIZE) as usize,
-            LEVEL_SIZE - 1
+            r_grid.get_width().saturating_sub(1)
         ),
         0
     );
@@ -145,7 +144,7 @@ fn s_update_cursor_to_grid_position(
     let grid_pos_z = cmp::max(
         cmp::min(
             ((local_position.z + (TILE_SIZE / 2.0)) / TILE_SIZE) as usize,
-            LEVEL_SIZE - 1
+            r_grid.get_depth().saturating_sub(1)
         ),
         0
     );
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::config::{StateGlobal, StateUserInputAllowed};
 use crate::editor::common::{
     PluginEditorData,
+    StateEditorLeavePrompt,
     StateEditorMode,
     EventCursorGridPositionChanged,
 };
@@ -12,11 +13,52 @@ use crate::editor::mode_tile::mode_tile::PluginEditorModeTile;
 use crate::editor::load_setup::PluginLoadSetup;
 use crate::editor::move_camera::PluginEditorCameraMovement;
 use crate::editor::mode_hedgehog::mode_hedgehog::PluginEditorModeHedgeclog;
+use crate::editor::mode_try::PluginEditorModeTry;
+use crate::editor::leave_editor::PluginLeaveEditor;
+use crate::editor::path_trace::PluginEditorPathTrace;
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput, EDITOR_MODES_EDITION};
 
 use crate::editor::common::SSetEditor;
 
 use crate::level::actions::edit_level::EventLevelEdidted;
+use crate::level::actions::edit_level_history::{EventLevelEditRedoAsked, EventLevelEditUndoAsked};
+use crate::level::actions::serialize::EventLevelSaveAsked;
+use crate::episode::serialize_episode::EventEpisodeSaveAsked;
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_QUIT: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyQ]),
+    description: "quit editor",
+};
+const BINDING_SAVE_LEVEL: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyS]),
+    description: "save level",
+};
+const BINDING_SAVE_EPISODE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyP]),
+    description: "save episode",
+};
+const BINDING_UNDO: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyU]),
+    description: "undo edit",
+};
+const BINDING_REDO: EditorBinding = EditorBinding{
+    input: EnumEditorInput::CtrlKey(KeyCode::KeyR),
+    description: "redo edit",
+};
+const BINDING_MODE_NORMAL: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::Escape]),
+    description: "mode normal",
+};
+const BINDING_MODE_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyT]),
+    description: "mode \"tile\"",
+};
+const BINDING_MODE_HEDGEHOG: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyY]),
+    description: "mode \"hedgehog\"",
+};
 
 // -- PLUGIN -----------------------------------------------------------------
 
@@ -24,20 +66,38 @@ pub struct PluginEditor;
 
 impl Plugin for PluginEditor{
     fn build(&self, app: &mut App){
+        app.add_plugins(PluginEditorData);
+        // first registered, first shown in help.
+        register_editor_bindings(
+            app,
+            EDITOR_MODES_EDITION,
+            "editor",
+            &[BINDING_QUIT, BINDING_SAVE_LEVEL, BINDING_SAVE_EPISODE, BINDING_UNDO, BINDING_REDO],
+        );
+        register_editor_bindings(
+            app,
+            EDITOR_MODES_EDITION,
+            "mode transition",
+            &[BINDING_MODE_NORMAL, BINDING_MODE_TILE, BINDING_MODE_HEDGEHOG],
+        );
         app
             // PLUGINS -------------------------------------------------------
-            .add_plugins(PluginEditorData)
             .add_plugins(PluginCursorToWorld)
             .add_plugins(PluginEditorUI)
             .add_plugins(PluginEditorModeTile)
             .add_plugins(PluginEditorModeHedgeclog)
+            .add_plugins(PluginEditorModeTry)
             .add_plugins(PluginLoadSetup)
             .add_plugins(PluginEditorCameraMovement)
+            .add_plugins(PluginLeaveEditor)
+            .add_plugins(PluginEditorPathTrace)
             // USER INPUT ----------------------------------------------------
             .add_systems(
                 Update,
                 (
-                    s_user_input_editor_global.in_set(SSetEditor::UserInput),
+                    s_user_input_editor_global
+                        .in_set(SSetEditor::UserInput)
+                        .run_if(not(in_state(StateEditorMode::Try))),
                     s_update_cursor_position_on_level_edited.run_if(
                         on_event::<EventLevelEdidted>()
                     )
@@ -45,7 +105,8 @@ impl Plugin for PluginEditor{
             )
             .configure_sets(Update, SSetEditor::UserInput .run_if(
                 in_state(StateGlobal::EditorRunning).and_then(
-                in_state(StateUserInputAllowed::Allowed)))
+                in_state(StateUserInputAllowed::Allowed)).and_then(
+                in_state(StateEditorLeavePrompt::Hidden)))
             );
     }
 }
@@ -56,27 +117,50 @@ impl Plugin for PluginEditor{
 
 fn s_user_input_editor_global(
     r_keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut s_state_global: ResMut<NextState<StateGlobal>>,
+    mut snext_leave_prompt: ResMut<NextState<StateEditorLeavePrompt>>,
     mut snext_editor_mode: ResMut<NextState<StateEditorMode>>,
-
+    mut e_level_save_asked: EventWriter<EventLevelSaveAsked>,
+    mut e_episode_save_asked: EventWriter<EventEpisodeSaveAsked>,
+    mut e_level_edit_undo_asked: EventWriter<EventLevelEditUndoAsked>,
+    mut e_level_edit_redo_asked: EventWriter<EventLevelEditRedoAsked>,
 ) {
-    // QUITTING EDITOR
-    if r_keyboard_input.just_pressed(KeyCode::KeyQ) {
-        s_state_global.set(StateGlobal::Game); 
+    // QUITTING EDITOR, once edits are kept, saved or discarded
+    if BINDING_QUIT.is_key_just_pressed(&r_keyboard_input) {
+        snext_leave_prompt.set(StateEditorLeavePrompt::Shown);
+        return
+    }
+    // SAVING LEVEL
+    if BINDING_SAVE_LEVEL.is_key_just_pressed(&r_keyboard_input) {
+        e_level_save_asked.send(EventLevelSaveAsked);
+        return
+    }
+    // SAVING EPISODE
+    if BINDING_SAVE_EPISODE.is_key_just_pressed(&r_keyboard_input) {
+        e_episode_save_asked.send(EventEpisodeSaveAsked);
+        return
+    }
+    // REDO
+    if BINDING_REDO.is_key_just_pressed(&r_keyboard_input) {
+        e_level_edit_redo_asked.send(EventLevelEditRedoAsked);
+        return
+    }
+    // UNDO
+    if BINDING_UNDO.is_key_just_pressed(&r_keyboard_input) {
+        e_level_edit_undo_asked.send(EventLevelEditUndoAsked);
         return
     }
     // TILE MODE
-    if r_keyboard_input.just_pressed(KeyCode::KeyT) {
+    if BINDING_MODE_TILE.is_key_just_pressed(&r_keyboard_input) {
         snext_editor_mode.set(StateEditorMode::Tile); 
         return
     }
     // HEDGECLOG MODE
-    if r_keyboard_input.just_pressed(KeyCode::KeyY) {
+    if BINDING_MODE_HEDGEHOG.is_key_just_pressed(&r_keyboard_input) {
         snext_editor_mode.set(StateEditorMode::Hedgehog); 
         return
     }
     // NORMAL MODE
-    if r_keyboard_input.just_pressed(KeyCode::Escape) {
+    if BINDING_MODE_NORMAL.is_key_just_pressed(&r_keyboard_input) {
         snext_editor_mode.set(StateEditorMode::Normal); 
     }
 } 
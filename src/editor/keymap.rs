@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::editor::common::StateEditorMode;
+
+// -- INPUT / BINDING --------------------------------------------------------
+
+// Keys never match while ctrl is held, so that "r" and "ctrl+r" are told
+// apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumEditorInput {
+    Keys(&'static [KeyCode]),
+    CtrlKey(KeyCode),
+    Mouse(MouseButton),
+}
+
+// Both checked by input systems and shown by editor help: help cannot drift
+// from what is actually bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EditorBinding {
+    pub input: EnumEditorInput,
+    pub description: &'static str,
+}
+
+// Modes a help section is shown in: the ones its input systems run in.
+pub const EDITOR_MODES_ALL: &[StateEditorMode] = &[
+    StateEditorMode::Normal,
+    StateEditorMode::Tile,
+    StateEditorMode::Hedgehog,
+    StateEditorMode::Try,
+];
+// Every mode but try, which plays the level rather than editing it.
+pub const EDITOR_MODES_EDITION: &[StateEditorMode] = &[
+    StateEditorMode::Normal,
+    StateEditorMode::Tile,
+    StateEditorMode::Hedgehog,
+];
+
+// -- RESSOURCES -------------------------------------------------------------
+
+// Bindings of one help section, active in modes only.
+#[derive(Debug, Clone)]
+pub struct EditorKeymapSection {
+    pub modes: &'static [StateEditorMode],
+    pub title: &'static str,
+    pub bindings: Vec<EditorBinding>,
+}
+
+// Filled by editor plugins on build, sections kept in registration order.
+#[derive(Resource, Debug, Default)]
+pub struct ResEditorKeymap {
+    pub sections: Vec<EditorKeymapSection>,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Bindings are appended to the section of same modes and title, if any.
+pub fn register_editor_bindings(
+    app: &mut App,
+    modes: &'static [StateEditorMode],
+    title: &'static str,
+    bindings: &[EditorBinding],
+) {
+    app.world_mut()
+        .get_resource_or_insert_with(ResEditorKeymap::default)
+        .register_bindings(modes, title, bindings);
+}
+
+impl ResEditorKeymap {
+    pub fn register_bindings(
+        &mut self,
+        modes: &'static [StateEditorMode],
+        title: &'static str,
+        bindings: &[EditorBinding],
+    ) {
+        match self.sections.iter_mut().find(|section| section.modes == modes && section.title == title) {
+            Some(section) => section.bindings.extend_from_slice(bindings),
+            None => self.sections.push(
+                EditorKeymapSection{modes, title, bindings: bindings.to_vec()}
+            ),
+        }
+    }
+
+    // Sections shared with other modes first, then the ones of mode only.
+    pub fn get_help_text(&self, mode: StateEditorMode) -> String {
+        let mode_sections = self.sections.iter().filter(|section| section.modes.contains(&mode));
+        let (shared_sections, own_sections): (Vec<_>, Vec<_>) = mode_sections
+            .partition(|section| section.modes.len() > 1);
+        let mut text = String::new();
+        for section in shared_sections.into_iter().chain(own_sections) {
+            text.push_str(&format!("* {}:\n\n", section.title));
+            let labels: Vec<String> = section.bindings.iter().map(EditorBinding::get_label).collect();
+            let width = labels.iter().map(String::len).max().unwrap_or(0) + 1;
+            for (label, binding) in labels.iter().zip(section.bindings.iter()) {
+                text.push_str(&format!("  {:<width$} {}\n", format!("{label}:"), binding.description));
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl EditorBinding {
+    pub fn is_key_just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>) -> bool {
+        let ctrl_pressed = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        match self.input {
+            EnumEditorInput::Keys(key_codes) => {
+                !ctrl_pressed && keyboard_input.any_just_pressed(key_codes.iter().copied())
+            },
+            EnumEditorInput::CtrlKey(key_code) => ctrl_pressed && keyboard_input.just_pressed(key_code),
+            EnumEditorInput::Mouse(_) => false,
+        }
+    }
+
+    pub fn is_mouse_just_pressed(&self, mouse_input: &ButtonInput<MouseButton>) -> bool {
+        match self.input {
+            EnumEditorInput::Mouse(mouse_button) => mouse_input.just_pressed(mouse_button),
+            _ => false,
+        }
+    }
+
+    pub fn get_label(&self) -> String {
+        match self.input {
+            EnumEditorInput::Keys(key_codes) => key_codes.iter()
+                .map(|key_code| get_key_label(*key_code))
+                .collect::<Vec<_>>()
+                .join("/"),
+            EnumEditorInput::CtrlKey(key_code) => format!("ctrl+{}", get_key_label(key_code)),
+            EnumEditorInput::Mouse(mouse_button) => format!("{mouse_button:?} click"),
+        }
+    }
+}
+
+// Letters as typed, other keys by name.
+pub fn get_key_label(key_code: KeyCode) -> String {
+    match key_code {
+        KeyCode::Escape => "ESC".to_string(),
+        KeyCode::Space => "SPACE".to_string(),
+        KeyCode::Backspace => "BACKSPACE".to_string(),
+        KeyCode::ArrowUp => "arrow up".to_string(),
+        KeyCode::ArrowDown => "arrow down".to_string(),
+        KeyCode::ArrowLeft => "arrow left".to_string(),
+        KeyCode::ArrowRight => "arrow right".to_string(),
+        key_code => {
+            let name = format!("{key_code:?}");
+            name.strip_prefix("Key").map_or(name.clone(), str::to_lowercase)
+        }
+    }
+}
+
+// One line prompt, as "a: keep   ESC: stay".
+pub fn get_bindings_inline(bindings: &[EditorBinding]) -> String {
+    bindings.iter()
+        .map(|binding| format!("{}: {}", binding.get_label(), binding.description))
+        .collect::<Vec<_>>()
+        .join("   ")
+}
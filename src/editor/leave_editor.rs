@@ -0,0 +1,145 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::config::{StateGlobal, StateUserInputAllowed};
+use crate::editor::common::StateEditorLeavePrompt;
+use crate::editor::keymap::{get_bindings_inline, EditorBinding, EnumEditorInput};
+use crate::editor::ui::MarkerEditorStatusGUI;
+use crate::episode::episode_definition::ResCurEpisode;
+use crate::episode::serialize_episode::EventEpisodeSaveAsked;
+use crate::level::actions::edit_level::{restore_level_grid, LevelSpawner};
+use crate::level::actions::serialize::EventLevelSaveAsked;
+use crate::level::definition::level_definition::ResCurrentLevelGrid;
+
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_KEEP: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyA]),
+    description: "keep edits",
+};
+const BINDING_SAVE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyS]),
+    description: "save edits",
+};
+const BINDING_DISCARD: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyD]),
+    description: "discard edits",
+};
+const BINDING_STAY: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::Escape]),
+    description: "stay",
+};
+
+// -- RESSOURCES -------------------------------------------------------------
+
+// Level as it was when editor was entered, restored if edits are discarded.
+#[derive(Resource, Default)]
+pub struct ResEditorLevelSnapshot {
+    pub level_grid: Option<ResCurrentLevelGrid>,
+}
+
+// Saves the episode of the level when there is one, as "p" does, the level
+// file otherwise.
+#[derive(SystemParam)]
+struct EditsSaveWriter<'w> {
+    r_cur_episode: Res<'w, ResCurEpisode>,
+    e_level_save_asked: EventWriter<'w, EventLevelSaveAsked>,
+    e_episode_save_asked: EventWriter<'w, EventEpisodeSaveAsked>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginLeaveEditor;
+
+impl Plugin for PluginLeaveEditor {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResEditorLevelSnapshot>()
+            .add_systems(OnEnter(StateGlobal::EditorRunning), s_take_level_snapshot)
+            .add_systems(OnExit(StateGlobal::EditorRunning), s_hide_leave_prompt)
+            .add_systems(OnEnter(StateEditorLeavePrompt::Shown), s_text_status_leave_prompt)
+            .add_systems(OnExit(StateEditorLeavePrompt::Shown), s_clear_text_status_leave_prompt)
+            .add_systems(
+                Update,
+                s_user_input_leave_prompt.run_if(
+                    in_state(StateGlobal::EditorRunning).and_then(
+                    in_state(StateUserInputAllowed::Allowed)).and_then(
+                    in_state(StateEditorLeavePrompt::Shown))
+                )
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+fn s_take_level_snapshot(
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_snapshot: ResMut<ResEditorLevelSnapshot>,
+) {
+    r_snapshot.level_grid = Some(r_grid.clone());
+}
+
+fn s_hide_leave_prompt(
+    mut snext_leave_prompt: ResMut<NextState<StateEditorLeavePrompt>>,
+) {
+    snext_leave_prompt.set(StateEditorLeavePrompt::Hidden);
+}
+
+fn s_text_status_leave_prompt(
+    mut q_text_status: Query<&mut Text, With <MarkerEditorStatusGUI>>,
+) {
+    if let Ok(mut text) = q_text_status.get_single_mut() {
+        text.sections[0].value = format!(
+            "Leaving editor:  {}",
+            get_bindings_inline(&[BINDING_KEEP, BINDING_SAVE, BINDING_DISCARD, BINDING_STAY])
+        );
+    }
+}
+
+fn s_clear_text_status_leave_prompt(
+    mut q_text_status: Query<&mut Text, With <MarkerEditorStatusGUI>>,
+) {
+    if let Ok(mut text) = q_text_status.get_single_mut() {
+        text.sections[0].value.clear();
+    }
+}
+
+fn s_user_input_leave_prompt(
+    mut spawner: LevelSpawner,
+    r_keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_snapshot: ResMut<ResEditorLevelSnapshot>,
+    mut snext_global: ResMut<NextState<StateGlobal>>,
+    mut snext_leave_prompt: ResMut<NextState<StateEditorLeavePrompt>>,
+    mut edits_save_writer: EditsSaveWriter,
+) {
+    if BINDING_STAY.is_key_just_pressed(&r_keyboard_input) {
+        snext_leave_prompt.set(StateEditorLeavePrompt::Hidden);
+        return
+    }
+    if BINDING_SAVE.is_key_just_pressed(&r_keyboard_input) {
+        edits_save_writer.send();
+    } else if BINDING_DISCARD.is_key_just_pressed(&r_keyboard_input) {
+        let Some(level_grid) = r_snapshot.level_grid.take() else {
+            return
+        };
+        restore_level_grid(&mut spawner, &mut r_grid, level_grid);
+    } else if !BINDING_KEEP.is_key_just_pressed(&r_keyboard_input) {
+        return
+    }
+    r_snapshot.level_grid = None;
+    snext_leave_prompt.set(StateEditorLeavePrompt::Hidden);
+    snext_global.set(StateGlobal::Game);
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl EditsSaveWriter<'_> {
+    fn send(&mut self) {
+        if self.r_cur_episode.episode_entity.is_some() {
+            self.e_episode_save_asked.send(EventEpisodeSaveAsked);
+        } else {
+            self.e_level_save_asked.send(EventLevelSaveAsked);
+        }
+    }
+}
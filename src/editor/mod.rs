@@ -4,8 +4,12 @@ pub mod common;
 pub mod ui;
 pub mod mode_tile;
 pub mod mode_hedgehog;
+pub mod mode_try;
 pub mod load_setup;
 pub mod move_camera;
+pub mod leave_editor;
+pub mod keymap;
+pub mod path_trace;
 
 
 
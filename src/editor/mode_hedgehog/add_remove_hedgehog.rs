@@ -24,6 +24,7 @@ use crate::level::actions::edit_level::{
     EventHedgehogRemovalAsked
 };
 use crate::editor::cursor_to_world::CursorGridPosition;
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput};
 
 use crate::level::definition::tiles::{EnumeTileBehaviour, TILE_SIZE};
 
@@ -43,12 +44,29 @@ struct ModeHedgehogLocalBuffer {
     pub hover_hedgehog_grid_position: GridPosition
 }
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_ADD_HEDGEHOG: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Left),
+    description: "add hedgehog",
+};
+const BINDING_REMOVE_HEDGEHOG: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Right),
+    description: "remove hedgehog",
+};
+
 pub struct PluginAddRemoveHedgehog;
 
 // -- PLUGIN -----------------------------------------------------------------
 
 impl Plugin for PluginAddRemoveHedgehog{
     fn build(&self, app: &mut App){
+        register_editor_bindings(
+            app,
+            &[StateEditorMode::Hedgehog],
+            "hedgehog mode",
+            &[BINDING_ADD_HEDGEHOG, BINDING_REMOVE_HEDGEHOG],
+        );
         app
             .add_event::<EventHedgehogCreated>()
             .add_event::<EventHedgehogRemoved>()
@@ -126,10 +144,10 @@ fn s_user_input(
     mut e_h_removed: EventWriter<EventHedgehogRemoved>,
     mut s_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
 ) {
-    if r_mouse_input.just_pressed(MouseButton::Left) {
+    if BINDING_ADD_HEDGEHOG.is_mouse_just_pressed(&r_mouse_input) {
         s_user_input_allowed.set(StateUserInputAllowed::NotAllowed);  // -> set to Allowed by add_remove_tile.create_tile
         e_h_created.send(EventHedgehogCreated);
-    } else if r_mouse_input.just_pressed(MouseButton::Right) {
+    } else if BINDING_REMOVE_HEDGEHOG.is_mouse_just_pressed(&r_mouse_input) {
         s_user_input_allowed.set(StateUserInputAllowed::NotAllowed);  // -> set to Allowed by add_remove_tile.remove_tile
         e_h_removed.send(EventHedgehogRemoved);
     }
@@ -24,6 +24,7 @@ use crate::editor::common::{
     StateEditorMode
 };
 use crate::editor::cursor_to_world::CursorGridPosition;
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput};
 use crate::editor::mode_tile::common::ModeTileLocalBuffer;
 
 
@@ -37,12 +38,33 @@ struct EventEditorTileCreationAsked;
 #[derive(Event)]
 struct EventEditorTileRemovalAsked;
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_ADD_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Left),
+    description: "add tile",
+};
+const BINDING_REMOVE_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Right),
+    description: "remove tile",
+};
+const BINDING_ROTATE_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyR]),
+    description: "rotate tile",
+};
+
 // -- PLUGIN -----------------------------------------------------------------
 
 pub struct PluginEditorAddRemoveTile;
 
 impl Plugin for PluginEditorAddRemoveTile{
     fn build(&self, app: &mut App){
+        register_editor_bindings(
+            app,
+            &[StateEditorMode::Tile],
+            "tile mode",
+            &[BINDING_ROTATE_TILE, BINDING_ADD_TILE, BINDING_REMOVE_TILE],
+        );
         app
             .add_event::<EventEditorTileCreationAsked>()
             .add_event::<EventEditorTileRemovalAsked>()
@@ -124,13 +146,13 @@ fn s_user_input(
     mut q_tile_creator: Query<&mut Transform, With <MarkerTileCreator>>,
     mut s_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
 ) {
-    if r_mouse_input.just_pressed(MouseButton::Left) {
+    if BINDING_ADD_TILE.is_mouse_just_pressed(&r_mouse_input) {
         s_user_input_allowed.set(StateUserInputAllowed::NotAllowed);  // -> set to Allowed by add_remove_tile.create_tile
         e_tile_created.send(EventEditorTileCreationAsked);
-    } else if r_mouse_input.just_pressed(MouseButton::Right) {
+    } else if BINDING_REMOVE_TILE.is_mouse_just_pressed(&r_mouse_input) {
         s_user_input_allowed.set(StateUserInputAllowed::NotAllowed);  // -> set to Allowed by add_remove_tile.remove_tile
         e_tile_removed.send(EventEditorTileRemovalAsked);
-    } else if r_keyboard_input.just_pressed(KeyCode::KeyR) {
+    } else if BINDING_ROTATE_TILE.is_key_just_pressed(&r_keyboard_input) {
         let mut transform = q_tile_creator.single_mut();
         transform.rotate_local_y(PI/2.0);
     }
@@ -13,8 +13,16 @@ use crate::editor::mode_tile::add_remove_tile::{
 };
 
 use crate::editor::common::SSetEditor;
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput};
 use crate::level::definition::tiles::MarkerTileOnLevel;
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_TILE_SELECTOR: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::Space]),
+    description: "choose tile",
+};
+
 // -- PLUGIN -----------------------------------------------------------------
 
 pub struct PluginEditorModeTile;
@@ -23,6 +31,7 @@ pub struct PluginEditorModeTile;
 
 impl Plugin for PluginEditorModeTile{
     fn build(&self, app: &mut App){
+        register_editor_bindings(app, &[StateEditorMode::Tile], "tile mode", &[BINDING_TILE_SELECTOR]);
         app
             .insert_resource(ModeTileLocalBuffer::default())
             .add_plugins(PluginEditorSelectTile)
@@ -79,7 +88,7 @@ fn s_user_input_editor_mode_tile(
 
 ) {
     // ENTERRING / LEAVING TILE SELECTION SCREEN.
-    if r_keyboard_input.just_pressed(KeyCode::Space) {
+    if BINDING_TILE_SELECTOR.is_key_just_pressed(&r_keyboard_input) {
         use StateEditorView::*;
         let next = match **s_editor_view {
             Level => TileSelector,
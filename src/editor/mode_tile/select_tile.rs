@@ -10,8 +10,10 @@ use crate::editor::common::{
     EventTileSelectedChanged,
     StateEditorLoaded,
     StateEditorView,
+    StateEditorMode,
     TRANSLATION_EDITOR_TILE_SELECTOR_ORIGIN,
 };
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput};
 use crate::editor::cursor_to_world::CursorToGroundCoordonate;
 
 use crate::editor::common::SSetEditor;
@@ -33,12 +35,20 @@ struct TilesSelectionGrid {
 #[derive(Component)]
 struct MarkerTileSelectorCube;
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_PICK_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Left),
+    description: "pick tile, in tile selector",
+};
+
 // -- PLUGIN -----------------------------------------------------------------
 
 pub struct PluginEditorSelectTile;
 
 impl Plugin for PluginEditorSelectTile{
     fn build(&self, app: &mut App){
+        register_editor_bindings(app, &[StateEditorMode::Tile], "tile mode", &[BINDING_PICK_TILE]);
         app
             .insert_resource(TilesSelectionGrid::default())
             .add_systems(OnEnter(StateEditorLoaded::Loading), s_load)
@@ -161,7 +171,7 @@ fn s_user_input(
     mut s_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
     
 ) {
-    if r_mouse_input.just_pressed(MouseButton::Left) {
+    if BINDING_PICK_TILE.is_mouse_just_pressed(&r_mouse_input) {
         s_user_input_allowed.set(StateUserInputAllowed::NotAllowed);  // -> re allowed by add_remove_tile.update_tile_creator_type
         s_next_editor_view.set(StateEditorView::Level);
         e_tile_selected_changed.send(
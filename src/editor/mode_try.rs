@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+
+use crate::config::{StateLevelLoaded, StateLevelOutcome};
+use crate::editor::common::{SSetEditor, StateEditorMode};
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput, EDITOR_MODES_EDITION};
+use crate::game::game::{
+    s_user_input_game_move,
+    KEYS_MOVE_DOWN,
+    KEYS_MOVE_LEFT,
+    KEYS_MOVE_RIGHT,
+    KEYS_MOVE_UP,
+};
+use crate::game::history::{EventLevelRestartAsked, EventTurnUndoAsked, ResTurnHistory};
+use crate::level::actions::edit_level::{restore_level_grid, LevelSpawner};
+use crate::level::definition::level_definition::ResCurrentLevelGrid;
+
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_MODE_TRY: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyX]),
+    description: "mode \"try\"",
+};
+const BINDING_LEAVE_MODE_TRY: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::Escape, KeyCode::KeyX]),
+    description: "back to previous mode",
+};
+const BINDING_UNDO_MOVE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyU, KeyCode::Backspace]),
+    description: "undo move",
+};
+const BINDING_RESTART: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyR]),
+    description: "restart level",
+};
+// moves are read by s_user_input_game_move, listed here for help only.
+const BINDINGS_MOVE: [EditorBinding; 4] = [
+    EditorBinding{input: EnumEditorInput::Keys(KEYS_MOVE_UP), description: "move up"},
+    EditorBinding{input: EnumEditorInput::Keys(KEYS_MOVE_DOWN), description: "move down"},
+    EditorBinding{input: EnumEditorInput::Keys(KEYS_MOVE_LEFT), description: "move left"},
+    EditorBinding{input: EnumEditorInput::Keys(KEYS_MOVE_RIGHT), description: "move right"},
+];
+
+// -- RESSOURCES -------------------------------------------------------------
+
+// Level as it was before trying it, and mode to go back to.
+#[derive(Resource, Default)]
+pub struct ResModeTryBuffer {
+    pub level_grid: Option<ResCurrentLevelGrid>,
+    pub previous_mode: Option<StateEditorMode>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginEditorModeTry;
+
+impl Plugin for PluginEditorModeTry {
+    fn build(&self, app: &mut App) {
+        register_editor_bindings(app, EDITOR_MODES_EDITION, "mode transition", &[BINDING_MODE_TRY]);
+        register_editor_bindings(app, &[StateEditorMode::Try], "try mode, level restored on leaving", &BINDINGS_MOVE);
+        register_editor_bindings(
+            app,
+            &[StateEditorMode::Try],
+            "try mode, level restored on leaving",
+            &[BINDING_UNDO_MOVE, BINDING_RESTART, BINDING_LEAVE_MODE_TRY],
+        );
+        app
+            .init_resource::<ResModeTryBuffer>()
+            .add_systems(OnEnter(StateEditorMode::Try), s_enter_mode_try)
+            .add_systems(OnExit(StateEditorMode::Try), s_exit_mode_try)
+            .add_systems(
+                Update,
+                (
+                    s_user_input_enter_mode_try
+                        .run_if(not(in_state(StateEditorMode::Try))),
+                    s_user_input_mode_try
+                        .run_if(in_state(StateEditorMode::Try)),
+                    // same moves as in game.
+                    s_user_input_game_move
+                        .run_if(in_state(StateEditorMode::Try)
+                        .and_then(in_state(StateLevelLoaded::Loaded))
+                        .and_then(in_state(StateLevelOutcome::Playing))),
+                ).in_set(SSetEditor::UserInput)
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+fn s_user_input_enter_mode_try(
+    r_keyboard_input: Res<ButtonInput<KeyCode>>,
+    s_editor_mode: Res<State<StateEditorMode>>,
+    mut r_try_buffer: ResMut<ResModeTryBuffer>,
+    mut snext_editor_mode: ResMut<NextState<StateEditorMode>>,
+) {
+    if BINDING_MODE_TRY.is_key_just_pressed(&r_keyboard_input) {
+        r_try_buffer.previous_mode = Some(*s_editor_mode.get());
+        snext_editor_mode.set(StateEditorMode::Try);
+    }
+}
+
+// Undo / restart stay available once the level is over, as in game.
+fn s_user_input_mode_try(
+    r_keyboard_input: Res<ButtonInput<KeyCode>>,
+    r_try_buffer: Res<ResModeTryBuffer>,
+    mut snext_editor_mode: ResMut<NextState<StateEditorMode>>,
+    mut e_turn_undo_asked: EventWriter<EventTurnUndoAsked>,
+    mut e_level_restart_asked: EventWriter<EventLevelRestartAsked>,
+) {
+    if BINDING_LEAVE_MODE_TRY.is_key_just_pressed(&r_keyboard_input) {
+        snext_editor_mode.set(r_try_buffer.previous_mode.unwrap_or(StateEditorMode::Normal));
+    }
+    else if BINDING_UNDO_MOVE.is_key_just_pressed(&r_keyboard_input) {
+        e_turn_undo_asked.send(EventTurnUndoAsked);
+    }
+    else if BINDING_RESTART.is_key_just_pressed(&r_keyboard_input) {
+        e_level_restart_asked.send(EventLevelRestartAsked);
+    }
+}
+
+fn s_enter_mode_try(
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_try_buffer: ResMut<ResModeTryBuffer>,
+    mut r_history: ResMut<ResTurnHistory>,
+    mut snext_level_outcome: ResMut<NextState<StateLevelOutcome>>,
+) {
+    r_try_buffer.level_grid = Some(r_grid.clone());
+    r_history.clear();
+    snext_level_outcome.set(StateLevelOutcome::Playing);
+}
+
+fn s_exit_mode_try(
+    mut spawner: LevelSpawner,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_try_buffer: ResMut<ResModeTryBuffer>,
+    mut r_history: ResMut<ResTurnHistory>,
+    mut snext_level_outcome: ResMut<NextState<StateLevelOutcome>>,
+) {
+    r_history.clear();
+    snext_level_outcome.set(StateLevelOutcome::Playing);
+    let Some(level_grid) = r_try_buffer.level_grid.take() else {
+        return
+    };
+    restore_level_grid(&mut spawner, &mut r_grid, level_grid);
+}
@@ -14,8 +14,9 @@ use crate::level::definition::camera::{
 
 use crate::editor::common::{
     EventEditorSubSystemLoaded, StateEditorLoaded,
-    TRANSLATION_EDITOR_TILE_SELECTOR_ORIGIN, StateEditorView
+    TRANSLATION_EDITOR_TILE_SELECTOR_ORIGIN, StateEditorView, StateEditorMode
 };
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput, EDITOR_MODES_EDITION};
 
 use crate::config::{StateGlobal, StateLevelLoaded};
 
@@ -24,6 +25,33 @@ use crate::level::definition::tiles::TILE_SIZE;
 #[derive(Component)]
 pub struct MarkerCameraInfoEditorTileSelectorView;
 
+// -- BINDINGS ---------------------------------------------------------------
+
+const BINDING_PAN_LEFT: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyH, KeyCode::ArrowLeft]),
+    description: "pan left",
+};
+const BINDING_PAN_RIGHT: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyL, KeyCode::ArrowRight]),
+    description: "pan right",
+};
+const BINDING_PAN_UP: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyK, KeyCode::ArrowUp]),
+    description: "pan up",
+};
+const BINDING_PAN_DOWN: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyJ, KeyCode::ArrowDown]),
+    description: "pan down",
+};
+const BINDING_ZOOM: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyB]),
+    description: "zoom",
+};
+const BINDING_UNZOOM: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyN]),
+    description: "unzoom",
+};
+
 // -- PLUGIN -----------------------------------------------------------------
 
 pub struct PluginEditorCameraMovement;
@@ -32,6 +60,12 @@ pub struct PluginEditorCameraMovement;
 
 impl Plugin for PluginEditorCameraMovement{
     fn build(&self, app: &mut App){
+        register_editor_bindings(
+            app,
+            EDITOR_MODES_EDITION,
+            "camera",
+            &[BINDING_PAN_LEFT, BINDING_PAN_RIGHT, BINDING_PAN_UP, BINDING_PAN_DOWN, BINDING_ZOOM, BINDING_UNZOOM],
+        );
         app
             .add_systems(OnEnter(StateEditorLoaded::Loading) , s_load)
             .add_systems(
@@ -40,8 +74,9 @@ impl Plugin for PluginEditorCameraMovement{
             )
             .add_systems(
                 Update,
-                s_pan_camera 
-                .run_if(in_state(StateGlobal::EditorRunning))
+                // try mode moves hedgehogs with the same keys.
+                s_pan_camera
+                .run_if(in_state(StateGlobal::EditorRunning).and_then(not(in_state(StateEditorMode::Try))))
             )
             .add_systems(
                 OnEnter(StateEditorView::TileSelector),
@@ -82,34 +117,34 @@ fn s_pan_camera(
 
     let (mut cam_projection, mut cam_transform) = camera_query.single_mut();
 
-    if r_keyboard_input.just_pressed(KeyCode::KeyH) || r_keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+    if BINDING_PAN_LEFT.is_key_just_pressed(&r_keyboard_input) {
         translate_camera(
             &mut cam_transform,
             Vec3{ x: -TILE_SIZE, y: 0.0, z: TILE_SIZE }
         );
     }
-    else if r_keyboard_input.just_pressed(KeyCode::KeyL) || r_keyboard_input.just_pressed(KeyCode::ArrowRight) {
+    else if BINDING_PAN_RIGHT.is_key_just_pressed(&r_keyboard_input) {
         translate_camera(
             &mut cam_transform,
             Vec3{ x: TILE_SIZE, y: 0.0, z: -TILE_SIZE }
         );
     }
-    else if r_keyboard_input.just_pressed(KeyCode::KeyK) || r_keyboard_input.just_pressed(KeyCode::ArrowUp) {
+    else if BINDING_PAN_UP.is_key_just_pressed(&r_keyboard_input) {
         translate_camera(
             &mut cam_transform,
             Vec3{ x: -TILE_SIZE, y: 0.0, z: -TILE_SIZE }
         );
     }
-    else if r_keyboard_input.just_pressed(KeyCode::KeyJ) || r_keyboard_input.just_pressed(KeyCode::ArrowDown) {
+    else if BINDING_PAN_DOWN.is_key_just_pressed(&r_keyboard_input) {
         translate_camera(
             &mut cam_transform,
             Vec3{ x: TILE_SIZE, y: 0.0, z: TILE_SIZE }
         );
     }
-    else if r_keyboard_input.just_pressed(KeyCode::KeyB) {
+    else if BINDING_ZOOM.is_key_just_pressed(&r_keyboard_input) {
         zoom_camera(&mut cam_projection, ZoomCameraMode::Zoom);
     }
-    else if r_keyboard_input.just_pressed(KeyCode::KeyN) {
+    else if BINDING_UNZOOM.is_key_just_pressed(&r_keyboard_input) {
         zoom_camera(&mut cam_projection, ZoomCameraMode::Unzoom);
     }
 
@@ -0,0 +1,209 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::config::StateGlobal;
+use crate::editor::common::{SSetEditor, StateEditorMode};
+use crate::editor::keymap::{register_editor_bindings, EditorBinding, EnumEditorInput, EDITOR_MODES_ALL};
+use crate::editor::ui::MarkerEditorStatusGUI;
+use crate::game::history::ResTurnHistory;
+use crate::level::actions::edit_level::EventLevelEdidted;
+use crate::level::definition::level_definition::{GridPosition, ResCurrentLevelGrid};
+use crate::level::definition::tiles::{
+    get_tile_transform_on_grid,
+    EnumTileOrientation,
+    EnumeTileBehaviour,
+    TILE_SIZE,
+    TILE_WIDTH,
+};
+use crate::puzzle::path_trace::PathTrace;
+use crate::puzzle::puzzle_state::{EnumDirection, PuzzleState};
+use crate::puzzle::solver::{solve, EnumSolverResult, SolverBounds};
+
+// -- CONST / BINDINGS -------------------------------------------------------
+
+// Just above floor tiles, below hedgehogs.
+const PATH_TRACE_OVERLAY_HEIGHT: f32 = TILE_WIDTH + 0.05;
+const PATH_TRACE_COLOR_USED: Color = Color::srgb(0.1, 0.9, 0.2);
+const PATH_TRACE_COLOR_UNUSED: Color = Color::srgba(0.9, 0.1, 0.1, 0.5);
+
+// Solved within the frame: a level out of these bounds is no editor test
+// level, and gets no trace rather than a frozen editor.
+const PATH_TRACE_SOLVER_BOUNDS: SolverBounds = SolverBounds{
+    max_move_count: 50,
+    max_state_count: 20_000,
+};
+
+const BINDING_PATH_TRACE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyG]),
+    description: "show / hide tiles used by solution, by played moves in try mode",
+};
+
+// -- EVENT / COMPONENT ------------------------------------------------------
+
+#[derive(Event, Debug)]
+pub struct EventPathTraceAsked;
+
+// Colored cell laid over a tile of the level, by path trace.
+#[derive(Component)]
+pub struct MarkerPathTraceOverlay;
+
+// Everything overlays are spawned and despawned with.
+#[derive(SystemParam)]
+struct PathTraceOverlays<'w, 's> {
+    commands: Commands<'w, 's>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    q_overlays: Query<'w, 's, Entity, With<MarkerPathTraceOverlay>>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginEditorPathTrace;
+
+impl Plugin for PluginEditorPathTrace {
+    fn build(&self, app: &mut App) {
+        register_editor_bindings(app, EDITOR_MODES_ALL, "path trace", &[BINDING_PATH_TRACE]);
+        app
+            .add_event::<EventPathTraceAsked>()
+            .add_systems(OnExit(StateGlobal::EditorRunning), s_clear_path_trace)
+            .add_systems(
+                Update,
+                (
+                    s_user_input_path_trace.in_set(SSetEditor::UserInput),
+                    s_toggle_path_trace.run_if(on_event::<EventPathTraceAsked>()),
+                    // trace of the level before edition is misleading.
+                    s_clear_path_trace.run_if(on_event::<EventLevelEdidted>()),
+                )
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+fn s_user_input_path_trace(
+    r_keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut e_path_trace_asked: EventWriter<EventPathTraceAsked>,
+) {
+    if BINDING_PATH_TRACE.is_key_just_pressed(&r_keyboard_input) {
+        e_path_trace_asked.send(EventPathTraceAsked);
+    }
+}
+
+// In try mode, moves played since try mode was entered are traced, the
+// shortest solution of the level otherwise.
+fn s_toggle_path_trace(
+    mut overlays: PathTraceOverlays,
+    r_grid: Res<ResCurrentLevelGrid>,
+    r_history: Res<ResTurnHistory>,
+    s_editor_mode: Res<State<StateEditorMode>>,
+    mut q_text_status: Query<&mut Text, With <MarkerEditorStatusGUI>>,
+    mut e_path_trace_asked: EventReader<EventPathTraceAsked>,
+) {
+    e_path_trace_asked.clear();
+    if !overlays.q_overlays.is_empty() {
+        for entity in overlays.q_overlays.iter() {
+            overlays.commands.entity(entity).despawn();
+        }
+        return
+    }
+    let (trace_name, moves, start_state) = match s_editor_mode.get() {
+        StateEditorMode::Try => (
+            "Played path",
+            r_history.directions.clone(),
+            PuzzleState::from_level_grid(r_history.snapshots.first().unwrap_or(&r_grid)),
+        ),
+        _ => {
+            let start_state = PuzzleState::from_level_grid(&r_grid);
+            match solve(&start_state, &PATH_TRACE_SOLVER_BOUNDS) {
+                EnumSolverResult::Solved(moves) => ("Solution path", moves, start_state),
+                result => {
+                    set_status_text(&mut q_text_status, format!("No path traced: {result:?}"));
+                    return
+                }
+            }
+        }
+    };
+    let path_trace = PathTrace::from_moves(&start_state, &moves);
+    let unused_tile_count = spawn_path_trace_overlays(&mut overlays, &r_grid, &path_trace);
+    set_status_text(
+        &mut q_text_status,
+        format!("{trace_name}: {} moves, {unused_tile_count} tiles unused ({})", moves.len(), get_moves_text(&moves)),
+    );
+}
+
+fn s_clear_path_trace(
+    mut commands: Commands,
+    q_overlays: Query<Entity, With<MarkerPathTraceOverlay>>,
+) {
+    for entity in q_overlays.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// One overlay per tile hedgehogs can walk on: the more a tile is stepped
+// on, the more opaque. Returns how many of them are never stepped on.
+// Fire is left out too: stepping on it loses the level.
+fn spawn_path_trace_overlays(
+    overlays: &mut PathTraceOverlays,
+    r_grid: &ResCurrentLevelGrid,
+    path_trace: &PathTrace,
+) -> usize {
+    let mesh = overlays.meshes.add(Plane3d::default().mesh().size(TILE_SIZE * 0.9, TILE_SIZE * 0.9));
+    let max_step_count = path_trace.get_max_step_count().max(1);
+    let mut unused_tile_count = 0;
+    for (x, row) in r_grid.level_grid.iter().enumerate() {
+        for (z, grid_tile) in row.iter().enumerate() {
+            if !is_tile_traced(grid_tile.tile_behaviour) {
+                continue
+            }
+            let grid_position = GridPosition{x, z};
+            let step_count = path_trace.get_step_count(&grid_position);
+            let color = if step_count == 0 {
+                unused_tile_count += 1;
+                PATH_TRACE_COLOR_UNUSED
+            } else {
+                PATH_TRACE_COLOR_USED.with_alpha(0.3 + 0.6 * step_count as f32 / max_step_count as f32)
+            };
+            let mut transform = get_tile_transform_on_grid(&grid_position, EnumTileOrientation::Deg0);
+            transform.translation.y += PATH_TRACE_OVERLAY_HEIGHT;
+            overlays.commands.spawn(
+                (
+                    PbrBundle {
+                        mesh: mesh.clone(),
+                        material: overlays.materials.add(
+                            StandardMaterial {
+                                base_color: color,
+                                alpha_mode: AlphaMode::Blend,
+                                unlit: true,
+                                ..default()
+                            }
+                        ),
+                        transform,
+                        ..default()
+                    },
+                    MarkerPathTraceOverlay,
+                )
+            );
+        }
+    }
+    unused_tile_count
+}
+
+fn is_tile_traced(tile_behaviour: EnumeTileBehaviour) -> bool {
+    matches!(
+        tile_behaviour,
+        EnumeTileBehaviour::TileBFloor | EnumeTileBehaviour::TileBWater | EnumeTileBehaviour::TileBExit
+    )
+}
+
+fn get_moves_text(moves: &[EnumDirection]) -> String {
+    moves.iter().map(|direction| format!("{direction:?}")).collect::<Vec<_>>().join(" ")
+}
+
+fn set_status_text(q_text_status: &mut Query<&mut Text, With <MarkerEditorStatusGUI>>, value: String) {
+    if let Ok(mut text) = q_text_status.get_single_mut() {
+        text.sections[0].value = value;
+    }
+}
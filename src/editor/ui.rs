@@ -8,6 +8,15 @@ use crate::editor::common::{
     MarkerEditorGUI,
     EventEditorSubSystemSetup,
 };
+use crate::editor::keymap::ResEditorKeymap;
+use crate::level::actions::serialize::EventLevelSaved;
+use crate::episode::serialize_episode::EventEpisodeSaved;
+
+// -- COMPONENTS -------------------------------------------------------------
+
+// Bottom line of the editor, feedback on last editor command.
+#[derive(Component)]
+pub struct MarkerEditorStatusGUI;
 
 // -- PLUGIN -----------------------------------------------------------------
 
@@ -18,54 +27,38 @@ impl Plugin for PluginEditorUI{
         app
             .add_systems(OnEnter(StateEditorLoaded::LoadedAndSetuping), setup)
             .add_systems(OnExit(StateGlobal::EditorRunning), s_teardown)
-            .add_systems(OnEnter(StateGlobal::EditorRunning), s_text_by_mode_normal)  // FIXME: hack! shall be removed.
+            .add_systems(OnEnter(StateGlobal::EditorRunning), s_text_help)
             .add_systems(
-                OnEnter(StateEditorMode::Normal),
-                s_text_by_mode_normal.run_if(in_state(StateGlobal::EditorRunning))
+                Update,
+                s_text_help.run_if(
+                    in_state(StateGlobal::EditorRunning).and_then(
+                    state_changed::<StateEditorMode>)
+                )
             )
             .add_systems(
-                OnEnter(StateEditorMode::Tile),
-                s_text_by_mode_tile.run_if(in_state(StateGlobal::EditorRunning))
+                Update,
+                s_text_status_on_level_saved.run_if(
+                    in_state(StateGlobal::EditorRunning).and_then(
+                    on_event::<EventLevelSaved>())
+                )
+            )
+            .add_systems(
+                Update,
+                s_text_status_on_episode_saved.run_if(
+                    in_state(StateGlobal::EditorRunning).and_then(
+                    on_event::<EventEpisodeSaved>())
+                )
             );
 
     }
 }
 
-// -- TEXT BY MODE CONSTANTS -------------------------------------------------
+// -- TEXT CONSTANTS --------------------------------------------------------
 
-const TEXT_MODE_HEADER_1: &str = r#"Level editor
+const TEXT_HEADER: &str = r#"Level editor
 
 current mode: "#;
 
-const TEXT_MODE_HEADER_2: &str = r#"
-
-q:   quit editor
-
-* mode transition:
-
-  ESC: mode normal
-  t:   mode "tile"
-  h:   mode "hedgehog"
-  x:   mode "try"
-
-* camera: 
-
-  arrows/hjkl: pan
-  b/n: zoom/unzoom
-
-"#;
-
-const TEXT_MODE_NORMAL: &str = r#"
-"#;
-
-const TEXT_MODE_TILE: &str = r#"* tile mode
-
-  SPACE:       choose tile
-  r:           rotate tile
-  Left click:  add tile.
-  Right click: remove tile.
-"#;
-
 // -- SYTEMS -----------------------------------------------------------------
 
 fn setup(
@@ -90,40 +83,79 @@ fn setup(
             MarkerEditorGUI,
         )
     );
+    commands.spawn(
+        (
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 20.0,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                ..default()
+            }),
+            MarkerEditorStatusGUI,
+        )
+    );
     e_editor_subsystem_setup.send(EventEditorSubSystemSetup);
 }
 
 fn s_teardown(
     mut commands: Commands,
     q_text_loading_editor: Query<Entity, With <MarkerEditorGUI>>,
+    q_text_status: Query<Entity, With <MarkerEditorStatusGUI>>,
 ) {
     commands.entity(q_text_loading_editor.single()).despawn();
+    commands.entity(q_text_status.single()).despawn();
 }
 
+fn s_text_status_on_level_saved(
+    mut q_text_status: Query<&mut Text, With <MarkerEditorStatusGUI>>,
+    mut e_level_saved: EventReader<EventLevelSaved>,
+) {
+    let Ok(mut text) = q_text_status.get_single_mut() else {
+        return
+    };
+    for e in e_level_saved.read() {
+        text.sections[0].value = match &e.result {
+            Ok(path) => format!("Level saved: {path}"),
+            Err(error) => format!("Level NOT saved: {error}"),
+        };
+    }
+}
 
-fn s_text_by_mode_normal(
-    mut q_editor_text: Query<&mut Text, With <MarkerEditorGUI>>,
-    s_editor_mode: Res<State<StateEditorMode>>,
+fn s_text_status_on_episode_saved(
+    mut q_text_status: Query<&mut Text, With <MarkerEditorStatusGUI>>,
+    mut e_episode_saved: EventReader<EventEpisodeSaved>,
 ) {
-    let mut text = q_editor_text.single_mut();
-    let text = &mut text.sections[0].value;
-    text.clear();
-    text.push_str(TEXT_MODE_HEADER_1);
-    text.push_str(&format!("{:?}", s_editor_mode.get()));
-    text.push_str(TEXT_MODE_HEADER_2);
-    text.push_str(TEXT_MODE_NORMAL);
+    let Ok(mut text) = q_text_status.get_single_mut() else {
+        return
+    };
+    for e in e_episode_saved.read() {
+        text.sections[0].value = match &e.result {
+            Ok(path) => format!("Episode saved: {path}"),
+            Err(error) => format!("Episode NOT saved: {error}"),
+        };
+    }
 }
 
-fn s_text_by_mode_tile(
+
+// Help is generated from the bindings registered for current mode.
+fn s_text_help(
     mut q_editor_text: Query<&mut Text, With <MarkerEditorGUI>>,
+    r_keymap: Res<ResEditorKeymap>,
     s_editor_mode: Res<State<StateEditorMode>>,
 ) {
-    let mut text = q_editor_text.single_mut();
+    let Ok(mut text) = q_editor_text.get_single_mut() else {
+        return
+    };
     let text = &mut text.sections[0].value;
     text.clear();
-    text.push_str(TEXT_MODE_HEADER_1);
-    text.push_str(&format!("{:?}", s_editor_mode.get()));
-    text.push_str(TEXT_MODE_HEADER_2);
-    text.push_str(TEXT_MODE_TILE);
+    text.push_str(TEXT_HEADER);
+    text.push_str(&format!("{:?}\n\n", s_editor_mode.get()));
+    text.push_str(&r_keymap.get_help_text(*s_editor_mode.get()));
 }
-
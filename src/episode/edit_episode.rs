@@ -3,6 +3,12 @@ use uuid::Uuid;
 use crate::episode::episode_definition::*;
 use crate::level::actions::load_run_level::create_new_level;
 use crate::app::common::*;
+use crate::episode::serialize_episode::{
+    load_episode_description_from_file,
+    spawn_episode_description,
+};
+
+const CONSTRUCT_EPISODE_NAME: &str = "construct";
 
 
 pub fn s_create_new_episode(
@@ -13,21 +19,55 @@ pub fn s_create_new_episode(
     create_new_episode(
         &mut commands,
         &r_episode_location,
-        "construct",
-        "construct"
+        CONSTRUCT_EPISODE_NAME,
+        CONSTRUCT_EPISODE_NAME
+    );
+}
+
+// Construct episode is kept between sessions: loaded back if already saved.
+pub fn s_load_or_create_construct_episode(
+    mut commands: Commands,
+    r_episode_location: Res<ResEpisodeLocation>,
+    r_type_registry: Res<AppTypeRegistry>,
+) {
+    let file_path = get_episode_file_path(
+        &r_episode_location.episode_location,
+        CONSTRUCT_EPISODE_NAME
+    );
+    if std::path::Path::new(&file_path).exists() {
+        match load_episode_description_from_file(&file_path, &r_type_registry) {
+            Ok(episode_description) => {
+                spawn_episode_description(&mut commands, &episode_description);
+                return
+            },
+            Err(error) => error!("Construct episode not loaded, new one created: {error}"),
+        }
+    }
+    create_new_episode(
+        &mut commands,
+        &r_episode_location,
+        CONSTRUCT_EPISODE_NAME,
+        CONSTRUCT_EPISODE_NAME
     );
 }
 
 pub fn s_create_origin_level_on_curr_episode(
     mut commands: Commands,
+    mut r_cur_episode: ResMut<ResCurEpisode>,
     mut r_cur_episode_grid: ResMut<ResCurEpisodeGrid>,
+    mut q_episode_grids: Query<&mut EpisodeGrid>,
 ) {
     let x = DEFAULT_EPISODE_START_LOCATION[0];
     let y = DEFAULT_EPISODE_START_LOCATION[1];
+    if r_cur_episode_grid.episode_grid[x][y].is_some() {
+        return
+    }
     create_level_on_curr_episode_at_position(
         x, y, 
         &mut commands,
-        &mut r_cur_episode_grid
+        &mut r_cur_episode,
+        &mut r_cur_episode_grid,
+        &mut q_episode_grids,
     );
 }
 
@@ -53,13 +93,12 @@ fn create_new_episode(
             EpisodeInfo{
                 level_name: episode_name.to_string(),
                 // level_path: "assets/level_desc/truc.ron".to_string()
-                level_path: format!(
-                    "assets/{:?}/{:?}.ron",
-                    r_episode_location.episode_location,
+                level_path: get_episode_file_path(
+                    &r_episode_location.episode_location,
                     episode_filename
                 )
             },
-            EpisodeUID{ uid: Uuid::new_v4() },
+            EpisodeUID{ uid },
             EpisodeGrid{ episode_grid }
         )
     );
@@ -71,10 +110,18 @@ fn create_level_on_curr_episode_at_position(
     pos_x: usize,
     pos_y: usize,
     commands: &mut Commands,
+    r_cur_episode: &mut ResMut<ResCurEpisode>,
     r_cur_episode_grid: &mut ResMut<ResCurEpisodeGrid>,
+    q_episode_grids: &mut Query<&mut EpisodeGrid>,
 ){
-    let level_uid = create_new_level(commands);
+    let (level_uid, level_entity) = create_new_level(commands);
     r_cur_episode_grid.episode_grid[pos_x][pos_y] = Some(level_uid);
+    r_cur_episode.level_entities.insert(level_uid, level_entity);
+    // episode entity is what gets saved.
+    if let Some(mut episode_grid) = r_cur_episode.episode_entity
+        .and_then(|entity| q_episode_grids.get_mut(entity).ok()) {
+        episode_grid.episode_grid[pos_x][pos_y] = Some(level_uid);
+    }
     //TODO: add checks on 'pos_x' 'pos_y' -> level size...
 }
 
@@ -1,6 +1,7 @@
 
 use bevy::prelude::*;
 use crate::episode::episode_definition::PluginEpsiodeDefinition;
+use crate::episode::serialize_episode::PluginSerializeEpisode;
 // use crate::episode::load_run_episode
 
 // -- PLUGIN -----------------------------------------------------------------
@@ -9,7 +10,9 @@ pub struct PluginEpsiode;
 
 impl Plugin for PluginEpsiode {
     fn build(&self, app: &mut App) {
-        app.add_plugins(PluginEpsiodeDefinition);
+        app
+            .add_plugins(PluginEpsiodeDefinition)
+            .add_plugins(PluginSerializeEpisode);
     }
 }
 
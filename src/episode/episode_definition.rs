@@ -1,6 +1,9 @@
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use uuid::Uuid;
 
+use crate::level::definition::level_definition::LevelDescription;
+
 // -- CONSTS -----------------------------------------------------------------
 
 pub const EPISODE_DEFAULT_HORIZONTAL_SIZE: usize = 10;
@@ -10,18 +13,21 @@ pub const DEFAULT_EPISODE_START_LOCATION: [usize; 2] = [0, 5];
 
 // -- COMPONENTS -------------------------------------------------------------
 
-#[derive(Component, Default, Debug, Clone)]
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
 pub struct EpisodeInfo{
     pub level_name: String,
-    pub level_path: String,
+    pub level_path: String, // episode file, from crate root.
 }
 
-#[derive(Component, Default, Debug, Clone)]
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
 pub struct EpisodeUID{
     pub uid: Uuid,
 }
 
-#[derive(Component, Default, Debug, Clone)]
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
 pub struct EpisodeGrid{
     pub episode_grid: [
         [Option<Uuid>; EPISODE_DEFAULT_HORIZONTAL_SIZE];
@@ -29,12 +35,60 @@ pub struct EpisodeGrid{
     ],
 }
 
-#[derive(Component, Default, Debug, Clone)]
+// Whole episode as written on disk: components of the episode entity and
+// every level referenced by its grid.
+#[derive(Default, Clone)]
 pub struct EpisodeDescription{
-    pub episode_grid: [
-        [Option<Uuid>; EPISODE_DEFAULT_HORIZONTAL_SIZE];
-        EPISODE_DEFAULT_VERTICAL_SIZE
-    ],
+    pub episode_info: EpisodeInfo,
+    pub episode_uid: EpisodeUID,
+    pub episode_grid: EpisodeGrid,
+    pub levels: Vec<LevelDescription>,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Path of an episode file, from crate root.
+pub fn get_episode_file_path(episode_location: &str, episode_filename: &str) -> String {
+    format!("assets/{episode_location}/{episode_filename}.ron")
+}
+
+impl EpisodeGrid {
+    // Every level uid of the grid, in grid order.
+    pub fn iter_level_uids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.episode_grid.iter().flatten().flatten().copied()
+    }
+}
+
+impl EpisodeDescription {
+    // What makes the episode unplayable, levels included, empty if nothing
+    // does.
+    pub fn get_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut level_uids: Vec<Uuid> = Vec::new();
+        for level_description in self.levels.iter() {
+            if level_uids.contains(&level_description.uuid) {
+                errors.push(format!("level {} is written twice", level_description.uuid));
+            }
+            level_uids.push(level_description.uuid);
+            for error in level_description.get_errors() {
+                errors.push(format!("level {}: {error}", level_description.uuid));
+            }
+        }
+        for level_uid in self.episode_grid.iter_level_uids() {
+            if !level_uids.contains(&level_uid) {
+                errors.push(format!("level {level_uid} of the grid is missing"));
+            }
+        }
+        let grid_level_uids: Vec<Uuid> = self.episode_grid.iter_level_uids().collect();
+        for level_uid in level_uids.iter().filter(|level_uid| !grid_level_uids.contains(level_uid)) {
+            errors.push(format!("level {level_uid} is not on the grid"));
+        }
+        let [x, y] = DEFAULT_EPISODE_START_LOCATION;
+        if self.episode_grid.episode_grid[x][y].is_none() {
+            errors.push(format!("no level at start location [{x}][{y}]"));
+        }
+        errors
+    }
 }
 
 // -- RESSOURCES -------------------------------------------------------------
@@ -43,6 +97,15 @@ pub struct EpisodeDescription{
 pub struct ResCurEpisode{
     pub episode_entity: Option<Entity>,
     pub episode_uid: Option<Uuid>,
+    // levels of the episode grid, filled when the episode is run.
+    pub level_entities: HashMap<Uuid, Entity>, // ready to be run.
+    pub missing_level_uids: Vec<Uuid>, // neither spawned nor on disk.
+}
+
+impl ResCurEpisode {
+    pub fn is_level_ready(&self, level_uid: &Uuid) -> bool {
+        self.level_entities.contains_key(level_uid)
+    }
 }
 
 #[derive(Resource, Default)]
@@ -61,7 +124,10 @@ impl Plugin for PluginEpsiodeDefinition {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<ResCurEpisode>()
-            .init_resource::<ResCurEpisodeGrid>();
+            .init_resource::<ResCurEpisodeGrid>()
+            .register_type::<EpisodeInfo>()
+            .register_type::<EpisodeUID>()
+            .register_type::<EpisodeGrid>();
     }
 }
 
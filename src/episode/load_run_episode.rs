@@ -1,64 +1,89 @@
 use bevy::prelude::*;
-use uuid::Uuid;
 use crate::episode::episode_definition::*;
 use crate::level::definition::level_definition::*;
-use crate::level::actions::load_run_level::run_level;
+use crate::level::actions::load_run_level::spawn_level_description;
+use crate::level::level::ResLevelToLoad;
+use crate::level::actions::serialize::{
+    get_level_description_file_path,
+    load_level_description_from_file,
+};
 
 pub fn s_run_only_episode(
+    mut commands: Commands,
+    r_type_registry: Res<AppTypeRegistry>,
     q_episodes: Query<(Entity, &EpisodeUID, &EpisodeGrid)>,
+    q_levels: Query<(Entity, &LevelUid), With<LevelGrid>>,
     mut r_cur_episode: ResMut<ResCurEpisode>,
     mut r_cur_episode_grid: ResMut<ResCurEpisodeGrid>,
 ) {
-    
-    // will panic if more than one episode.
-    let (entity, episode_uid, episode_grid) = q_episodes.single();
+    let Ok(episode) = q_episodes.get_single() else {
+        warn!("No single episode to run.");
+        return
+    };
     run_episode(
+        &mut commands,
+        &r_type_registry,
         &mut r_cur_episode,
         &mut r_cur_episode_grid,
-        &entity,
-        &episode_uid,
-        &episode_grid
+        episode,
+        &q_levels,
     );
 }
 
+// Origin level is run by the level loading, as any level of the episode.
 pub fn s_run_origin_level_on_curr_episode(
     r_cur_episode_grid: Res<ResCurEpisodeGrid>,
-    mut r_cur_level: ResMut<ResCurrentLevel>,
-    mut r_cur_level_grid: ResMut<ResCurrentLevelGrid>,
-    q_levels: Query<(Entity, &LevelUid, &LevelGrid)>,
+    mut r_level_to_load: ResMut<ResLevelToLoad>,
 ) {
 
     let x = DEFAULT_EPISODE_START_LOCATION[0];
     let y = DEFAULT_EPISODE_START_LOCATION[1];
 
-    let level_uid_option = r_cur_episode_grid.episode_grid[x][y];
-    let level_uid: Uuid;
-
-    // FIXME: rust syntaxic sugar to do this better.
-    if level_uid_option.is_some() {
-        level_uid = level_uid_option.unwrap();
-    } else {
+    let Some(level_uid) = r_cur_episode_grid.episode_grid[x][y] else {
         return
-    }
-    run_level(level_uid, &mut r_cur_level, &mut r_cur_level_grid, &q_levels);
+    };
+    r_level_to_load.level_uid = Some(level_uid);
 }
 
 // -- FUNCS ------------------------------------------------------------------
 
 
+// Every level of the episode grid is resolved to its level entity: already
+// spawned, or spawned from its level file. Tiles and hedgehogs carry their
+// level uid too, only entities with a LevelGrid are levels. Levels found
+// nowhere are reported in ResCurEpisode.missing_level_uids.
 pub fn run_episode(
+    commands: &mut Commands,
+    type_registry: &AppTypeRegistry,
     r_cur_episode: &mut ResMut<ResCurEpisode>,
     r_cur_episode_grid: &mut ResMut<ResCurEpisodeGrid>,
-    episode_entity: &Entity,
-    episode_uid: &EpisodeUID,
-    episode_grid: &EpisodeGrid,
-    // missing: a query with all levels!
-
+    (episode_entity, episode_uid, episode_grid): (Entity, &EpisodeUID, &EpisodeGrid),
+    q_levels: &Query<(Entity, &LevelUid), With<LevelGrid>>,
 ) {
     r_cur_episode.episode_uid = Some(episode_uid.uid);
-    r_cur_episode.episode_entity = Some(*episode_entity);
+    r_cur_episode.episode_entity = Some(episode_entity);
     r_cur_episode_grid.episode_grid = episode_grid.episode_grid.clone();
-    // TODO: find all levels and load them !!
-    // if none, skip!
-}
+    r_cur_episode.level_entities.clear();
+    r_cur_episode.missing_level_uids.clear();
 
+    for level_uid in episode_grid.iter_level_uids() {
+        let level_entity = q_levels.iter()
+            .find(|(_, i_level_uid)| i_level_uid.uid == Some(level_uid))
+            .map(|(entity, _)| entity);
+        if let Some(level_entity) = level_entity {
+            r_cur_episode.level_entities.insert(level_uid, level_entity);
+            continue
+        }
+        let file_path = get_level_description_file_path(level_uid);
+        match load_level_description_from_file(&file_path, type_registry) {
+            Ok(level_description) => {
+                let level_entity = spawn_level_description(commands, &level_description);
+                r_cur_episode.level_entities.insert(level_uid, level_entity);
+            },
+            Err(error) => {
+                error!("Level {level_uid} of episode {} is missing: {error}", episode_uid.uid);
+                r_cur_episode.missing_level_uids.push(level_uid);
+            }
+        }
+    }
+}
@@ -1,31 +1,253 @@
-use bevy::{prelude::*, tasks::IoTaskPool};
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+use uuid::Uuid;
 
-use crate::app::builder_bevy_app::ResTypeRegister;
 use crate::level::definition::level_definition::{
-    LevelGrid, LevelUid, ResCurrentLevelGrid, ResCurrentLevel
+    LevelDescription, LevelGrid, LevelUid, ResCurrentLevelGrid, ResCurrentLevel
 };
 
-use crate::episode::episode_definition::{EpisodeGrid, ResCurEpisode};
-use crate::level::actions::load_run_level::dump_current_level_ressource_to_components;
+use crate::episode::episode_definition::{
+    EpisodeDescription,
+    EpisodeGrid,
+    EpisodeInfo,
+    EpisodeUID,
+    ResCurEpisode,
+};
+use crate::level::actions::load_run_level::{
+    dump_current_level_ressource_to_components,
+    spawn_level_description,
+};
+use crate::level::actions::serialize::{get_level_description_from_level_grid, write_file};
+
+// -- EVENT ------------------------------------------------------------------
+
+// Saves current episode to the file of its EpisodeInfo.
+#[derive(Event)]
+pub struct EventEpisodeSaveAsked;
 
+// Ok: path of the written file, Err: why it was not written.
+#[derive(Event)]
+pub struct EventEpisodeSaved {
+    pub result: Result<String, String>,
+}
+
+// file_path is from crate root, as EpisodeInfo.level_path.
+#[derive(Event)]
+pub struct EventEpisodeLoadAsked {
+    pub file_path: String,
+}
 
+// Ok: uid of the spawned episode, Err: why it was not loaded.
+#[derive(Event)]
+pub struct EventEpisodeLoaded {
+    pub result: Result<Uuid, String>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginSerializeEpisode;
+
+impl Plugin for PluginSerializeEpisode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<EventEpisodeSaveAsked>()
+            .add_event::<EventEpisodeSaved>()
+            .add_event::<EventEpisodeLoadAsked>()
+            .add_event::<EventEpisodeLoaded>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        s_update_current_episode_description,
+                        s_dump_episode_description_to_file,
+                    ).chain().run_if(on_event::<EventEpisodeSaveAsked>()),
+                    s_load_episode_description_from_file.run_if(on_event::<EventEpisodeLoadAsked>()),
+                )
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+// Current level may have been edited since it was run: its level entity is
+// updated before being written.
 fn s_update_current_episode_description(
     mut r_cur_level: ResMut<ResCurrentLevel>,
     mut r_cur_level_grid: ResMut<ResCurrentLevelGrid>,
-    r_type_register: Res<ResTypeRegister>,
-    r_cur_episode_grid: Res<ResCurEpisode>,
     mut q_levels: Query<(Entity, &mut LevelGrid)>
 ){
-    // TODO: any way to serialize query to pass it to subfunction?
     dump_current_level_ressource_to_components(
         &mut r_cur_level,
         &mut r_cur_level_grid,
         &mut q_levels
     );
+}
 
+fn s_dump_episode_description_to_file(
+    r_type_registry: Res<AppTypeRegistry>,
+    r_cur_episode: Res<ResCurEpisode>,
+    q_episodes: Query<(&EpisodeInfo, &EpisodeUID, &EpisodeGrid)>,
+    q_levels: Query<(&LevelUid, &LevelGrid)>,
+    mut e_episode_save_asked: EventReader<EventEpisodeSaveAsked>,
+    mut e_episode_saved: EventWriter<EventEpisodeSaved>,
+){
+    e_episode_save_asked.clear();
+
+    let result = match r_cur_episode.episode_entity.map(|entity| q_episodes.get(entity)) {
+        None | Some(Err(_)) => Err("no current episode".to_string()),
+        Some(Ok((episode_info, episode_uid, episode_grid))) => {
+            get_episode_description(episode_info, episode_uid, episode_grid, &q_levels)
+                .and_then(|episode_description| {
+                    serialize_episode_description(&episode_description, &r_type_registry)
+                })
+                .and_then(|serialized| write_file(&episode_info.level_path, &serialized))
+                .map(|_| episode_info.level_path.clone())
+        }
+    };
+    match &result {
+        Ok(path) => info!("Episode saved to {path}."),
+        Err(error) => error!("Episode not saved: {error}"),
+    }
+    e_episode_saved.send(EventEpisodeSaved{result});
+}
+
+fn s_load_episode_description_from_file(
+    mut commands: Commands,
+    r_type_registry: Res<AppTypeRegistry>,
+    mut e_episode_load_asked: EventReader<EventEpisodeLoadAsked>,
+    mut e_episode_loaded: EventWriter<EventEpisodeLoaded>,
+){
+    for event in e_episode_load_asked.read() {
+        let result = load_episode_description_from_file(&event.file_path, &r_type_registry)
+            .map(|episode_description| {
+                spawn_episode_description(&mut commands, &episode_description);
+                episode_description.episode_uid.uid
+            });
+        if let Err(error) = &result {
+            error!("Episode not loaded: {error}");
+        }
+        e_episode_loaded.send(EventEpisodeLoaded{result});
+    }
+}
+
+// -- FUNCS ------------------------------------------------------------------
 
+// Gathers the episode and every level of its grid. A level referenced by the
+// grid without any level entity is an error: the file would be unplayable.
+pub fn get_episode_description(
+    episode_info: &EpisodeInfo,
+    episode_uid: &EpisodeUID,
+    episode_grid: &EpisodeGrid,
+    q_levels: &Query<(&LevelUid, &LevelGrid)>,
+) -> Result<EpisodeDescription, String> {
+    let mut levels = Vec::new();
+    for level_uid in episode_grid.iter_level_uids() {
+        let level_grid = q_levels.iter()
+            .find(|(i_level_uid, _)| i_level_uid.uid == Some(level_uid))
+            .map(|(_, level_grid)| level_grid)
+            .ok_or(format!("level {level_uid} not found"))?;
+        levels.push(get_level_description_from_level_grid(level_grid, level_uid));
+    }
+    Ok(
+        EpisodeDescription {
+            episode_info: episode_info.clone(),
+            episode_uid: episode_uid.clone(),
+            episode_grid: episode_grid.clone(),
+            levels,
+        }
+    )
+}
+
+// Serializes episode as a scene: one entity for the episode, one per level.
+pub fn serialize_episode_description(
+    episode_description: &EpisodeDescription,
+    type_registry: &AppTypeRegistry,
+) -> Result<String, String> {
+    let mut scene_world = World::new();
+    scene_world.insert_resource(type_registry.clone());
+    scene_world.spawn(
+        (
+            episode_description.episode_info.clone(),
+            episode_description.episode_uid.clone(),
+            episode_description.episode_grid.clone(),
+        )
+    );
+    for level_description in episode_description.levels.iter() {
+        scene_world.spawn(level_description.clone());
+    }
+    let scene = DynamicScene::from_world(&scene_world);
+    scene.serialize(&type_registry.read()).map_err(|error| error.to_string())
+}
+
+// Reads back a file written by serialize_episode_description.
+pub fn deserialize_episode_description(
+    serialized: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<EpisodeDescription, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(serialized, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+
+    let mut episode_info: Option<EpisodeInfo> = None;
+    let mut episode_uid: Option<EpisodeUID> = None;
+    let mut episode_grid: Option<EpisodeGrid> = None;
+    let mut levels = Vec::new();
+    for component in scene.entities.iter().flat_map(|entity| entity.components.iter()) {
+        let component = component.as_ref();
+        if let Some(value) = EpisodeInfo::from_reflect(component) {
+            episode_info = Some(value);
+        } else if let Some(value) = EpisodeUID::from_reflect(component) {
+            episode_uid = Some(value);
+        } else if let Some(value) = EpisodeGrid::from_reflect(component) {
+            episode_grid = Some(value);
+        } else if let Some(value) = LevelDescription::from_reflect(component) {
+            if !value.is_size_consistent() {
+                return Err(format!("level {}: grid does not match level size", value.uuid))
+            }
+            levels.push(value);
+        }
+    }
+    let (Some(episode_info), Some(episode_uid), Some(episode_grid)) =
+        (episode_info, episode_uid, episode_grid) else {
+        return Err("no episode found".to_string())
+    };
+    Ok(
+        EpisodeDescription {
+            episode_info,
+            episode_uid,
+            episode_grid,
+            levels,
+        }
+    )
+}
 
+// file_path is from crate root, as EpisodeInfo.level_path.
+pub fn load_episode_description_from_file(
+    file_path: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<EpisodeDescription, String> {
+    let serialized = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    deserialize_episode_description(&serialized, type_registry)
+        .map_err(|error| format!("{file_path}: {error}"))
 }
 
-fn s_dump_episode_description_to_file(){
+// Spawns episode entity and one entity per level, as create_new_episode and
+// create_new_level do. Levels keep their description to be spawned on run.
+pub fn spawn_episode_description(
+    commands: &mut Commands,
+    episode_description: &EpisodeDescription,
+) -> Entity {
+    for level_description in episode_description.levels.iter() {
+        spawn_level_description(commands, level_description);
+    }
+    commands.spawn(
+        (
+            episode_description.episode_info.clone(),
+            episode_description.episode_uid.clone(),
+            episode_description.episode_grid.clone(),
+        )
+    ).id()
 }
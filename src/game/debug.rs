@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::config::{StateGlobal, StateLevelLoaded};
+use crate::config::{StateGlobal, StateLevelLoaded, StateLevelOutcome};
 use crate::editor::common::StateEditorLoaded;
 
 pub struct PluginDebug;
@@ -20,6 +20,8 @@ impl Plugin for PluginDebug{
 
         app.add_systems(OnEnter(StateLevelLoaded::Loading), print_loading_level);
         app.add_systems(OnEnter(StateLevelLoaded::Loaded),  print_loaded_level);
+        app.add_systems(OnEnter(StateLevelOutcome::Won),  print_level_won);
+        app.add_systems(OnEnter(StateLevelOutcome::Lost),  print_level_lost);
     } 
 }
 
@@ -44,3 +46,11 @@ fn print_loading_level() {
 fn print_loaded_level() {
     info!("Loaded level.");
 }
+
+fn print_level_won() {
+    info!("Level won.");
+}
+
+fn print_level_lost() {
+    info!("Level lost.");
+}
@@ -1,29 +1,82 @@
 use bevy::prelude::*;
-use crate::config::{StateGlobal, StateUserInputAllowed};
+use crate::config::{StateGlobal, StateLevelLoaded, StateLevelOutcome, StateUserInputAllowed};
+use crate::game::turn::PluginTurn;
+use crate::puzzle::puzzle_state::EnumDirection;
+use crate::game::history::PluginTurnHistory;
+use crate::game::navigation::PluginLevelNavigation;
+use crate::game::replay::{EnumReplayInput, GameInputWriter, PluginReplay};
+
+// Shared with editor "try" mode, which shows them in its help.
+pub const KEYS_MOVE_UP: &[KeyCode] = &[KeyCode::ArrowUp, KeyCode::KeyW];
+pub const KEYS_MOVE_DOWN: &[KeyCode] = &[KeyCode::ArrowDown, KeyCode::KeyS];
+pub const KEYS_MOVE_LEFT: &[KeyCode] = &[KeyCode::ArrowLeft, KeyCode::KeyA];
+pub const KEYS_MOVE_RIGHT: &[KeyCode] = &[KeyCode::ArrowRight, KeyCode::KeyD];
 
 pub struct PluginGame;
 
 impl Plugin for PluginGame{
     fn build(&self, app: &mut App){
-        app.add_systems(
-            Update, 
-            (
-                s_user_input_game_global
-                    .run_if(in_state(StateGlobal::Game)
-                    .and_then(in_state(StateUserInputAllowed::Allowed))),
-            )
-        );
+        app
+            .add_plugins(PluginTurn)
+            .add_plugins(PluginTurnHistory)
+            .add_plugins(PluginLevelNavigation)
+            .add_plugins(PluginReplay)
+            .add_systems(OnEnter(StateLevelLoaded::Loaded), s_reset_level_outcome)
+            .add_systems(
+                Update, 
+                // chained: asked in the order they are resolved, moves first.
+                (
+                    s_user_input_game_move
+                        .run_if(in_state(StateGlobal::Game)
+                        .and_then(in_state(StateUserInputAllowed::Allowed))
+                        .and_then(in_state(StateLevelLoaded::Loaded))
+                        .and_then(in_state(StateLevelOutcome::Playing))),
+                    s_user_input_game_global
+                        .run_if(in_state(StateGlobal::Game)
+                        .and_then(in_state(StateUserInputAllowed::Allowed))),
+                ).chain()
+            );
     }
 }
 
 
+// Undo / restart stay available once the level is over.
 fn s_user_input_game_global(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut state_global: ResMut<NextState<StateGlobal>>,
-
+    mut game_input_writer: GameInputWriter,
 ) {
     if keyboard_input.pressed(KeyCode::KeyE) {
         state_global.set(StateGlobal::EditorRequested); 
     }
-    // add move hedghog, restart, undo.
+    else if keyboard_input.any_just_pressed([KeyCode::KeyU, KeyCode::Backspace]) {
+        game_input_writer.send(EnumReplayInput::Undo);
+    }
+    else if keyboard_input.just_pressed(KeyCode::KeyR) {
+        game_input_writer.send(EnumReplayInput::Restart);
+    }
+}
+
+pub fn s_user_input_game_move(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut game_input_writer: GameInputWriter,
+) {
+    let direction = if keyboard_input.any_just_pressed(KEYS_MOVE_UP.iter().copied()) {
+        EnumDirection::Up
+    } else if keyboard_input.any_just_pressed(KEYS_MOVE_DOWN.iter().copied()) {
+        EnumDirection::Down
+    } else if keyboard_input.any_just_pressed(KEYS_MOVE_LEFT.iter().copied()) {
+        EnumDirection::Left
+    } else if keyboard_input.any_just_pressed(KEYS_MOVE_RIGHT.iter().copied()) {
+        EnumDirection::Right
+    } else {
+        return
+    };
+    game_input_writer.send(EnumReplayInput::from_direction(direction));
+}
+
+fn s_reset_level_outcome(
+    mut snext_level_outcome: ResMut<NextState<StateLevelOutcome>>,
+) {
+    snext_level_outcome.set(StateLevelOutcome::Playing);
 }
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+
+use crate::config::{StateGlobal, StateLevelLoaded, StateLevelOutcome};
+use crate::level::definition::level_definition::ResCurrentLevelGrid;
+use crate::level::actions::edit_level::{respawn_hedgehogs, LevelSpawner};
+use crate::game::turn::{get_level_outcome, s_resolve_turn};
+use crate::puzzle::puzzle_state::EnumDirection;
+
+// -- CONST / ENUM / EVENT / COMPONENT / RESSOURCE ---------------------------
+
+// State of the level grid before every resolved turn, oldest first, and
+// direction played from it: the moves of the current playthrough.
+// Entities stored in snapshots are not reused: hedgehogs are respawned.
+#[derive(Resource, Debug, Default)]
+pub struct ResTurnHistory {
+    pub snapshots: Vec<ResCurrentLevelGrid>,
+    pub directions: Vec<EnumDirection>,
+}
+
+#[derive(Event, Debug)]
+pub struct EventTurnUndoAsked;
+
+#[derive(Event, Debug)]
+pub struct EventLevelRestartAsked;
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginTurnHistory;
+
+impl Plugin for PluginTurnHistory {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResTurnHistory>()
+            .add_event::<EventTurnUndoAsked>()
+            .add_event::<EventLevelRestartAsked>()
+            .add_systems(OnEnter(StateLevelLoaded::Loaded), s_clear_history)
+            .add_systems(OnEnter(StateGlobal::Game), s_clear_history)
+            .add_systems(
+                Update,
+                // inputs of a frame are resolved in the order they are
+                // asked (see GameInputWriter).
+                (
+                    s_undo_turn.run_if(on_event::<EventTurnUndoAsked>()),
+                    s_restart_level.run_if(on_event::<EventLevelRestartAsked>()),
+                ).chain().after(s_resolve_turn)
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+fn s_clear_history(mut r_history: ResMut<ResTurnHistory>) {
+    r_history.clear();
+}
+
+fn s_undo_turn(
+    mut spawner: LevelSpawner,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_history: ResMut<ResTurnHistory>,
+    mut snext_level_outcome: ResMut<NextState<StateLevelOutcome>>,
+    mut e_turn_undo_asked: EventReader<EventTurnUndoAsked>,
+) {
+    let mut snapshot: Option<ResCurrentLevelGrid> = None;
+    for _ in e_turn_undo_asked.read() {
+        if let Some(previous) = r_history.pop_turn() {
+            snapshot = Some(previous);
+        }
+    }
+    let Some(snapshot) = snapshot else {
+        return
+    };
+    restore_snapshot(&mut spawner, &mut r_grid, snapshot);
+    snext_level_outcome.set(get_level_outcome(&r_grid));
+}
+
+fn s_restart_level(
+    mut spawner: LevelSpawner,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_history: ResMut<ResTurnHistory>,
+    mut snext_level_outcome: ResMut<NextState<StateLevelOutcome>>,
+    mut e_level_restart_asked: EventReader<EventLevelRestartAsked>,
+) {
+    e_level_restart_asked.clear();
+    if r_history.snapshots.is_empty() {
+        return
+    }
+    let snapshot = r_history.snapshots.swap_remove(0);
+    r_history.clear();
+    restore_snapshot(&mut spawner, &mut r_grid, snapshot);
+    snext_level_outcome.set(get_level_outcome(&r_grid));
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl ResTurnHistory {
+    pub fn push_turn(&mut self, snapshot: ResCurrentLevelGrid, direction: EnumDirection) {
+        self.snapshots.push(snapshot);
+        self.directions.push(direction);
+    }
+
+    // Grid before the last turn, which is forgotten.
+    pub fn pop_turn(&mut self) -> Option<ResCurrentLevelGrid> {
+        self.directions.pop();
+        self.snapshots.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+        self.directions.clear();
+    }
+}
+
+// Replaces current grid by snapshot, despawning every hedgehog of the
+// current grid and respawning the ones of the snapshot (dead ones included).
+fn restore_snapshot(
+    spawner: &mut LevelSpawner,
+    r_grid: &mut ResCurrentLevelGrid,
+    snapshot: ResCurrentLevelGrid,
+) {
+    for hedgehog in r_grid.hedgehog_grid.iter().flatten() {
+        if let Some(entity) = hedgehog.hedgehog_entity {
+            spawner.commands.entity(entity).despawn();
+        }
+    }
+    *r_grid = snapshot;
+    respawn_hedgehogs(spawner, r_grid);
+}
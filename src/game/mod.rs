@@ -1,2 +1,6 @@
 pub mod debug;
 pub mod game;
+pub mod turn;
+pub mod history;
+pub mod navigation;
+pub mod replay;
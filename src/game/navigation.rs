@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::config::{StateGlobal, StateLevelLoaded};
+use crate::episode::episode_definition::{ResCurEpisode, ResCurEpisodeGrid};
+use crate::level::definition::level_definition::{
+    GridPosition,
+    ResCurrentLevelGrid,
+};
+use crate::level::definition::camera::EventCameraTransition;
+use crate::level::definition::tiles::TILE_SIZE;
+use crate::level::level::LevelSwitcher;
+use crate::puzzle::puzzle_state::EnumDirection;
+use crate::game::turn::EventLevelWon;
+
+// -- CONST / ENUM / EVENT / COMPONENT / RESSOURCE ---------------------------
+
+// Direction the current level was entered from, None if it was not reached
+// through another level.
+// left_level_size: tiles of the level left, along entered_toward.
+#[derive(Resource, Debug, Default)]
+pub struct ResLevelNavigation {
+    pub entered_toward: Option<EnumDirection>,
+    pub left_level_size: usize,
+}
+
+// Level won through an exit leading out of the episode grid.
+#[derive(Event, Debug)]
+pub struct EventEpisodeExited {
+    pub episode_uid: Uuid,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginLevelNavigation;
+
+impl Plugin for PluginLevelNavigation {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResLevelNavigation>()
+            .add_event::<EventEpisodeExited>()
+            .add_systems(
+                Update,
+                // levels won in editor "try" mode lead nowhere.
+                s_navigate_to_neighbour_level.run_if(
+                    in_state(StateGlobal::Game).and_then(on_event::<EventLevelWon>())
+                )
+            )
+            .add_systems(OnEnter(StateLevelLoaded::Loaded), s_camera_transition_on_level_entered);
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+// A level won through an exit on its edge leads to the level next to it in
+// the episode grid. Without any level there, the episode is exited.
+fn s_navigate_to_neighbour_level(
+    mut level_switcher: LevelSwitcher,
+    r_cur_episode: Res<ResCurEpisode>,
+    r_cur_episode_grid: Res<ResCurEpisodeGrid>,
+    mut r_level_navigation: ResMut<ResLevelNavigation>,
+    mut e_level_won: EventReader<EventLevelWon>,
+    mut e_episode_exited: EventWriter<EventEpisodeExited>,
+) {
+    let r_grid = &level_switcher.r_grid;
+    let Some(e) = e_level_won.read().last() else {
+        return
+    };
+    let Some(level_uid) = e.level_uid else {
+        return
+    };
+    let Some(direction) = get_exit_direction(&e.exit_position, r_grid.get_width(), r_grid.get_depth()) else {
+        return
+    };
+    let Some(neighbour_level_uid) = get_neighbour_level_uid(&r_cur_episode_grid, level_uid, direction) else {
+        if let Some(episode_uid) = r_cur_episode.episode_uid {
+            e_episode_exited.send(EventEpisodeExited{episode_uid});
+        }
+        return
+    };
+    if !r_cur_episode.is_level_ready(&neighbour_level_uid) {
+        warn!("Level {neighbour_level_uid} next to {level_uid} is not ready.");
+        return
+    }
+    r_level_navigation.entered_toward = Some(direction);
+    r_level_navigation.left_level_size = get_level_size_along(r_grid, direction);
+    level_switcher.r_level_to_load.level_uid = Some(neighbour_level_uid);
+    level_switcher.switch_level();
+}
+
+// Camera comes from the previous level, lying behind the entered one: both
+// are framed on their center, half a level apart each.
+fn s_camera_transition_on_level_entered(
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_level_navigation: ResMut<ResLevelNavigation>,
+    mut e_camera_transition: EventWriter<EventCameraTransition>,
+) {
+    let Some(direction) = r_level_navigation.entered_toward.take() else {
+        return
+    };
+    let level_size = r_level_navigation.left_level_size + get_level_size_along(&r_grid, direction);
+    let level_span = TILE_SIZE * level_size as f32 / 2.0;
+    e_camera_transition.send(
+        EventCameraTransition{
+            from_offset: -get_direction_vector(direction) * level_span,
+        }
+    );
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Edge of the level an exit lies on, None if exit is not on an edge.
+// Corners belong to their x edge.
+pub fn get_exit_direction(
+    exit_position: &GridPosition,
+    width: usize,
+    depth: usize,
+) -> Option<EnumDirection> {
+    if exit_position.x == 0 {
+        Some(EnumDirection::Up)
+    } else if exit_position.x + 1 == width {
+        Some(EnumDirection::Down)
+    } else if exit_position.z + 1 == depth {
+        Some(EnumDirection::Left)
+    } else if exit_position.z == 0 {
+        Some(EnumDirection::Right)
+    } else {
+        None
+    }
+}
+
+// Episode grid is oriented as level grids: [x][y], y going along z.
+pub fn get_neighbour_level_uid(
+    r_cur_episode_grid: &ResCurEpisodeGrid,
+    level_uid: Uuid,
+    direction: EnumDirection,
+) -> Option<Uuid> {
+    let episode_grid = &r_cur_episode_grid.episode_grid;
+    let (x, y) = episode_grid.iter().enumerate()
+        .find_map(|(x, row)| {
+            row.iter().position(|uid| *uid == Some(level_uid)).map(|y| (x, y))
+        })?;
+    let (x, y) = match direction {
+        EnumDirection::Up => (x.checked_sub(1)?, y),
+        EnumDirection::Down => (x + 1, y),
+        EnumDirection::Left => (x, y + 1),
+        EnumDirection::Right => (x, y.checked_sub(1)?),
+    };
+    *episode_grid.get(x)?.get(y)?
+}
+
+// Tiles of the level along direction: width along x, depth along z.
+pub fn get_level_size_along(r_grid: &ResCurrentLevelGrid, direction: EnumDirection) -> usize {
+    match direction {
+        EnumDirection::Up | EnumDirection::Down => r_grid.get_width(),
+        EnumDirection::Left | EnumDirection::Right => r_grid.get_depth(),
+    }
+}
+
+fn get_direction_vector(direction: EnumDirection) -> Vec3 {
+    match direction {
+        EnumDirection::Up => Vec3::NEG_X,
+        EnumDirection::Down => Vec3::X,
+        EnumDirection::Left => Vec3::Z,
+        EnumDirection::Right => Vec3::NEG_Z,
+    }
+}
@@ -0,0 +1,492 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+use uuid::Uuid;
+
+use crate::config::{StateGlobal, StateLevelLoaded, StateLevelOutcome, StateUserInputAllowed};
+use crate::game::history::{EventLevelRestartAsked, EventTurnUndoAsked};
+use crate::game::turn::{get_level_outcome, s_resolve_turn, EventLevelWon, EventTurnAsked};
+use crate::episode::episode_definition::ResCurEpisodeGrid;
+use crate::level::actions::serialize::write_file;
+use crate::level::level::ResLevelToLoad;
+use crate::level::definition::level_definition::{LevelDescription, ResCurrentLevel, ResCurrentLevelGrid};
+use crate::puzzle::puzzle_state::{EnumDirection, EnumPuzzleOutcome, PuzzleState};
+
+// Every input of a playthrough, to be played back in game or verified
+// against the level rules without any window (see level_tool verify).
+
+// -- CONST / ENUM -----------------------------------------------------------
+
+// Bumped on every format change, with its step in migrate_replay.
+pub const REPLAY_VERSION: u32 = 1;
+
+// From crate root, as save games: replays are not assets.
+pub const REPLAY_DEFAULT_DIRECTORY: &str = "replays";
+
+pub const REPLAY_DEFAULT_INPUTS_PER_SECOND: f32 = 4.0;
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumReplayInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    Undo,
+    Restart,
+}
+
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumReplayOutcome {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
+// Input asked to the game, sent along its turn, undo or restart event by
+// GameInputWriter: read in the order inputs are asked.
+#[derive(Event, Debug)]
+pub struct EventReplayInput {
+    pub input: EnumReplayInput,
+}
+
+// -- COMPONENT / RESSOURCE --------------------------------------------------
+
+// Inputs as they were asked, including the ones the game ignored (moves
+// changing nothing, moves once the level is over): played back the same way
+// they give the same result.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct Replay {
+    pub version: u32,
+    pub level_uid: Uuid,
+    pub inputs: Vec<EnumReplayInput>,
+    pub outcome: EnumReplayOutcome, // when the last input was recorded.
+}
+
+// directory None: nothing is written, replay is kept for the session only.
+// One file per level, overwritten every time the level is loaded again.
+#[derive(Resource, Default)]
+pub struct ResReplayRecording {
+    pub directory: Option<String>,
+    pub replay: Option<Replay>,
+}
+
+// Replay is played back once its level is loaded, user inputs are not
+// allowed meanwhile. One input per timer tick, at most one per frame so that
+// every input is resolved before the next one is sent. Playback ends with
+// its level: inputs left are never sent to the next one.
+#[derive(Resource)]
+pub struct ResReplayPlayback {
+    pub replay: Option<Replay>,
+    pub inputs_per_second: f32,
+    pub is_playing: bool,
+    pub next_input_idx: usize,
+    pub timer: Timer,
+}
+
+// Every game input, by player or playback, is asked through here: replays
+// record inputs of a frame in the order they are asked.
+#[derive(SystemParam)]
+pub struct GameInputWriter<'w> {
+    e_turn_asked: EventWriter<'w, EventTurnAsked>,
+    e_turn_undo_asked: EventWriter<'w, EventTurnUndoAsked>,
+    e_level_restart_asked: EventWriter<'w, EventLevelRestartAsked>,
+    e_replay_input: EventWriter<'w, EventReplayInput>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginReplay;
+
+impl Plugin for PluginReplay {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResReplayRecording>()
+            .init_resource::<ResReplayPlayback>()
+            .add_event::<EventReplayInput>()
+            .register_type::<Replay>()
+            .add_systems(
+                OnEnter(StateLevelLoaded::Loaded),
+                (
+                    s_start_replay_recording,
+                    s_start_replay_playback,
+                ).run_if(in_state(StateGlobal::Game))
+            )
+            .add_systems(
+                OnExit(StateLevelLoaded::Loaded),
+                s_stop_replay_playback.run_if(|r_playback: Res<ResReplayPlayback>| r_playback.is_playing)
+            )
+            .add_systems(
+                Update,
+                (
+                    // outcome of the turn, before the level is left for the
+                    // next one.
+                    s_record_replay.after(s_resolve_turn).run_if(
+                        in_state(StateGlobal::Game).and_then(in_state(StateLevelLoaded::Loaded))
+                    ),
+                    (
+                        s_play_replay.before(s_resolve_turn),
+                        s_end_replay_playback_on_level_won
+                            .after(s_resolve_turn)
+                            .run_if(on_event::<EventLevelWon>()),
+                    ).run_if(
+                        in_state(StateGlobal::Game)
+                        .and_then(in_state(StateLevelLoaded::Loaded))
+                        .and_then(|r_playback: Res<ResReplayPlayback>| r_playback.is_playing)
+                    ),
+                )
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+// Run after the origin level is chosen: the replay level is loaded instead,
+// if it is on the current episode.
+pub fn s_run_replay_level(
+    r_playback: Res<ResReplayPlayback>,
+    r_cur_episode_grid: Res<ResCurEpisodeGrid>,
+    mut r_level_to_load: ResMut<ResLevelToLoad>,
+) {
+    let Some(replay) = &r_playback.replay else {
+        return
+    };
+    let is_on_episode = r_cur_episode_grid.episode_grid.iter().flatten()
+        .any(|level_uid| *level_uid == Some(replay.level_uid));
+    if !is_on_episode {
+        error!("Replay level {} is not on the current episode, not played back.", replay.level_uid);
+        return
+    }
+    r_level_to_load.level_uid = Some(replay.level_uid);
+}
+
+fn s_start_replay_recording(
+    r_current_level: Res<ResCurrentLevel>,
+    mut r_recording: ResMut<ResReplayRecording>,
+) {
+    r_recording.replay = r_current_level.level_uid.map(Replay::new);
+}
+
+// Written whenever inputs or outcome change, and always once the level is
+// won: winning may leave the level on the next frame.
+fn s_record_replay(
+    r_type_registry: Res<AppTypeRegistry>,
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_recording: ResMut<ResReplayRecording>,
+    mut e_replay_input: EventReader<EventReplayInput>,
+    mut e_level_won: EventReader<EventLevelWon>,
+) {
+    let Some(replay) = &mut r_recording.replay else {
+        return
+    };
+    let input_count = replay.inputs.len();
+    replay.inputs.extend(e_replay_input.read().map(|e| e.input));
+    let is_level_won = e_level_won.read().count() > 0;
+    let outcome = EnumReplayOutcome::from(get_level_outcome(&r_grid));
+    if replay.inputs.len() == input_count && replay.outcome == outcome && !is_level_won {
+        return
+    }
+    replay.outcome = outcome;
+    let Some(directory) = &r_recording.directory else {
+        return
+    };
+    let Some(replay) = &r_recording.replay else {
+        return
+    };
+    let file_path = get_replay_file_path(directory, replay.level_uid);
+    let result = serialize_replay(replay, &r_type_registry)
+        .and_then(|serialized| write_file(&file_path, &serialized));
+    if let Err(error) = result {
+        error!("Replay not written: {error}");
+    }
+}
+
+fn s_start_replay_playback(
+    r_current_level: Res<ResCurrentLevel>,
+    mut r_playback: ResMut<ResReplayPlayback>,
+    mut snext_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
+) {
+    let Some(replay) = &r_playback.replay else {
+        return
+    };
+    if r_current_level.level_uid != Some(replay.level_uid) {
+        return
+    }
+    info!("Playing back replay of level {}: {} inputs.", replay.level_uid, replay.inputs.len());
+    let seconds_per_input = 1.0 / r_playback.inputs_per_second.max(f32::EPSILON);
+    r_playback.is_playing = true;
+    r_playback.next_input_idx = 0;
+    r_playback.timer = Timer::from_seconds(seconds_per_input, TimerMode::Repeating);
+    snext_user_input_allowed.set(StateUserInputAllowed::NotAllowed);
+}
+
+// Once every input is sent, the outcome reached is checked against the
+// recorded one one tick later, when the last input is resolved, unless the
+// level was won by it.
+fn s_play_replay(
+    time: Res<Time>,
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_playback: ResMut<ResReplayPlayback>,
+    mut snext_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
+    mut game_input_writer: GameInputWriter,
+) {
+    if !r_playback.timer.tick(time.delta()).just_finished() {
+        return
+    }
+    let Some(replay) = &r_playback.replay else {
+        r_playback.is_playing = false;
+        return
+    };
+    let Some(input) = replay.inputs.get(r_playback.next_input_idx).copied() else {
+        end_replay_playback(&mut r_playback, &r_grid);
+        snext_user_input_allowed.set(StateUserInputAllowed::Allowed);
+        return
+    };
+    game_input_writer.send(input);
+    r_playback.next_input_idx += 1;
+}
+
+// Level won by the last input: checked now, as the level may be left for
+// the next one before the next tick.
+fn s_end_replay_playback_on_level_won(
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut r_playback: ResMut<ResReplayPlayback>,
+    mut snext_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
+    mut e_level_won: EventReader<EventLevelWon>,
+) {
+    e_level_won.clear();
+    let Some(replay) = &r_playback.replay else {
+        return
+    };
+    if r_playback.next_input_idx < replay.inputs.len() {
+        return
+    }
+    end_replay_playback(&mut r_playback, &r_grid);
+    snext_user_input_allowed.set(StateUserInputAllowed::Allowed);
+}
+
+// Level left before every input was sent: the next level loading allows
+// user inputs again.
+fn s_stop_replay_playback(mut r_playback: ResMut<ResReplayPlayback>) {
+    if let Some(replay) = &r_playback.replay {
+        error!(
+            "Replay stopped: level {} left with {} inputs not played back.",
+            replay.level_uid,
+            replay.inputs.len().saturating_sub(r_playback.next_input_idx),
+        );
+    }
+    r_playback.replay = None;
+    r_playback.is_playing = false;
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl GameInputWriter<'_> {
+    pub fn send(&mut self, input: EnumReplayInput) {
+        match input {
+            EnumReplayInput::Undo => {
+                self.e_turn_undo_asked.send(EventTurnUndoAsked);
+            },
+            EnumReplayInput::Restart => {
+                self.e_level_restart_asked.send(EventLevelRestartAsked);
+            },
+            input => {
+                if let Some(direction) = input.get_direction() {
+                    self.e_turn_asked.send(EventTurnAsked{direction});
+                }
+            },
+        }
+        self.e_replay_input.send(EventReplayInput{input});
+    }
+}
+
+// Outcome reached is checked against the recorded one. Played back once:
+// the level is not played back when loaded again.
+fn end_replay_playback(r_playback: &mut ResReplayPlayback, r_grid: &ResCurrentLevelGrid) {
+    if let Some(replay) = &r_playback.replay {
+        let outcome = EnumReplayOutcome::from(get_level_outcome(r_grid));
+        if outcome == replay.outcome {
+            info!("Replay played back: {outcome:?} as recorded.");
+        } else {
+            error!("Replay played back: {outcome:?}, recorded {:?}.", replay.outcome);
+        }
+    }
+    r_playback.replay = None;
+    r_playback.is_playing = false;
+}
+
+impl Default for ResReplayPlayback {
+    fn default() -> Self {
+        ResReplayPlayback {
+            replay: None,
+            inputs_per_second: REPLAY_DEFAULT_INPUTS_PER_SECOND,
+            is_playing: false,
+            next_input_idx: 0,
+            timer: Timer::default(),
+        }
+    }
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Replay::new(Uuid::nil())
+    }
+}
+
+impl Replay {
+    pub fn new(level_uid: Uuid) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            level_uid,
+            inputs: Vec::new(),
+            outcome: EnumReplayOutcome::Playing,
+        }
+    }
+}
+
+impl EnumReplayInput {
+    pub fn from_direction(direction: EnumDirection) -> Self {
+        match direction {
+            EnumDirection::Up => EnumReplayInput::Up,
+            EnumDirection::Down => EnumReplayInput::Down,
+            EnumDirection::Left => EnumReplayInput::Left,
+            EnumDirection::Right => EnumReplayInput::Right,
+        }
+    }
+
+    pub fn get_direction(&self) -> Option<EnumDirection> {
+        match self {
+            EnumReplayInput::Up => Some(EnumDirection::Up),
+            EnumReplayInput::Down => Some(EnumDirection::Down),
+            EnumReplayInput::Left => Some(EnumDirection::Left),
+            EnumReplayInput::Right => Some(EnumDirection::Right),
+            EnumReplayInput::Undo | EnumReplayInput::Restart => None,
+        }
+    }
+}
+
+impl From<EnumPuzzleOutcome> for EnumReplayOutcome {
+    fn from(outcome: EnumPuzzleOutcome) -> Self {
+        match outcome {
+            EnumPuzzleOutcome::Playing => EnumReplayOutcome::Playing,
+            EnumPuzzleOutcome::Won => EnumReplayOutcome::Won,
+            EnumPuzzleOutcome::Lost => EnumReplayOutcome::Lost,
+        }
+    }
+}
+
+impl From<StateLevelOutcome> for EnumReplayOutcome {
+    fn from(outcome: StateLevelOutcome) -> Self {
+        match outcome {
+            StateLevelOutcome::Playing => EnumReplayOutcome::Playing,
+            StateLevelOutcome::Won => EnumReplayOutcome::Won,
+            StateLevelOutcome::Lost => EnumReplayOutcome::Lost,
+        }
+    }
+}
+
+pub fn get_replay_file_path(directory: &str, level_uid: Uuid) -> String {
+    format!("{directory}/{level_uid}.ron")
+}
+
+// Plays inputs on the level as the game does (see s_resolve_turn and
+// history): moves are ignored once the level is over, moves changing nothing
+// are not undone, restart goes back to the level start.
+pub fn play_replay(replay: &Replay, level_description: &LevelDescription) -> EnumReplayOutcome {
+    let start_state = PuzzleState::from_level_description(level_description);
+    let mut state = start_state.clone();
+    let mut previous_states: Vec<PuzzleState> = Vec::new();
+    for input in replay.inputs.iter() {
+        match input {
+            EnumReplayInput::Undo => {
+                if let Some(previous_state) = previous_states.pop() {
+                    state = previous_state;
+                }
+            },
+            EnumReplayInput::Restart => {
+                if !previous_states.is_empty() {
+                    state = start_state.clone();
+                    previous_states.clear();
+                }
+            },
+            input => {
+                let Some(direction) = input.get_direction() else {
+                    continue
+                };
+                if state.get_outcome() != EnumPuzzleOutcome::Playing {
+                    continue
+                }
+                let previous_state = state.clone();
+                if !state.step(direction).is_empty() {
+                    previous_states.push(previous_state);
+                }
+            },
+        }
+    }
+    EnumReplayOutcome::from(state.get_outcome())
+}
+
+// Replay still reaches its recorded outcome on the level: rules or level
+// changes broke it otherwise.
+pub fn verify_replay(replay: &Replay, level_description: &LevelDescription) -> Result<(), String> {
+    if replay.level_uid != level_description.uuid {
+        return Err(format!("replay of level {}, not {}", replay.level_uid, level_description.uuid))
+    }
+    match play_replay(replay, level_description) {
+        outcome if outcome == replay.outcome => Ok(()),
+        outcome => Err(format!("replay reaches {outcome:?}, recorded {:?}", replay.outcome)),
+    }
+}
+
+// Brings a replay of any older version up to REPLAY_VERSION.
+pub fn migrate_replay(replay: Replay) -> Result<Replay, String> {
+    match replay.version {
+        REPLAY_VERSION => Ok(replay),
+        version if version > REPLAY_VERSION => Err(
+            format!("replay version {version} is newer than supported {REPLAY_VERSION}")
+        ),
+        version => Err(format!("no migration from replay version {version}")),
+    }
+}
+
+// Serializes replay as a one entity scene.
+pub fn serialize_replay(
+    replay: &Replay,
+    type_registry: &AppTypeRegistry,
+) -> Result<String, String> {
+    let mut scene_world = World::new();
+    scene_world.insert_resource(type_registry.clone());
+    scene_world.spawn(replay.clone());
+    let scene = DynamicScene::from_world(&scene_world);
+    scene.serialize(&type_registry.read()).map_err(|error| error.to_string())
+}
+
+// Reads back a file written by serialize_replay, migrated to the current
+// version.
+pub fn deserialize_replay(
+    serialized: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<Replay, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(serialized, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+    let replay = scene.entities.iter()
+        .flat_map(|entity| entity.components.iter())
+        .find_map(|component| Replay::from_reflect(component.as_ref()))
+        .ok_or("no replay found".to_string())?;
+    migrate_replay(replay)
+}
+
+// file_path is from crate root.
+pub fn load_replay_from_file(
+    file_path: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<Replay, String> {
+    let serialized = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    deserialize_replay(&serialized, type_registry)
+        .map_err(|error| format!("{file_path}: {error}"))
+}
@@ -0,0 +1,261 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::config::StateLevelOutcome;
+use crate::level::definition::level_definition::{
+    GridPosition,
+    ResCurrentLevel,
+    ResCurrentLevelGrid,
+    LevelGridHedgehog,
+};
+use crate::level::definition::hedgehog::{
+    EnumHedgehogOnGrid,
+    MarkerHedgehogOnLevel,
+    ResHedgeHogInfo,
+    get_hedgehog_transform_on_grid,
+    get_hedgehog_color,
+};
+use crate::puzzle::puzzle_state::{
+    EnumDirection,
+    EnumPuzzleOutcome,
+    EnumStepEvent,
+    PuzzleState,
+};
+use crate::game::history::ResTurnHistory;
+
+// -- CONST / ENUM / EVENT ---------------------------------------------------
+
+#[derive(Event, Debug)]
+pub struct EventTurnAsked {
+    pub direction: EnumDirection,
+}
+
+#[derive(Event, Debug)]
+pub struct EventTurnResolved;
+
+// level_uid: level won, kept here as the current level may change before
+// the event is read.
+// exit_position: exit reached by the last rescued hedgehog.
+// move_count: turns played, undone ones excluded.
+#[derive(Event, Debug)]
+pub struct EventLevelWon {
+    pub level_uid: Option<Uuid>,
+    pub exit_position: GridPosition,
+    pub move_count: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HedgehogMove {
+    pub hedgehog_entity: Option<Entity>,
+    pub from: GridPosition,
+    pub to: GridPosition,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HedgehogDeath {
+    pub hedgehog_entity: Option<Entity>,
+    pub position: GridPosition,
+    pub cause: EnumHedgehogOnGrid,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HedgehogRescue {
+    pub hedgehog_entity: Option<Entity>,
+    pub position: GridPosition,
+}
+
+// Everything that happened during one turn, used to update entities.
+#[derive(Debug, Default, Clone)]
+pub struct TurnReport {
+    pub moves: Vec<HedgehogMove>,
+    pub deaths: Vec<HedgehogDeath>,
+    pub rescues: Vec<HedgehogRescue>,
+}
+
+// Hedgehog entities, following the turn report.
+#[derive(SystemParam)]
+pub struct HedgehogEntities<'w, 's> {
+    commands: Commands<'w, 's>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    r_hedgehog_info: Res<'w, ResHedgeHogInfo>,
+    q_hedgehogs: Query<
+        'w, 's,
+        (&'static mut GridPosition, &'static mut Transform, &'static Handle<StandardMaterial>),
+        With<MarkerHedgehogOnLevel>
+    >,
+}
+
+// Outcome of the level once it is over, announcing the won level.
+#[derive(SystemParam)]
+pub struct LevelOutcomeWriter<'w> {
+    r_current_level: Res<'w, ResCurrentLevel>,
+    snext_level_outcome: ResMut<'w, NextState<StateLevelOutcome>>,
+    e_level_won: EventWriter<'w, EventLevelWon>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginTurn;
+
+impl Plugin for PluginTurn {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<EventTurnAsked>()
+            .add_event::<EventTurnResolved>()
+            .add_event::<EventLevelWon>()
+            .add_systems(
+                Update,
+                s_resolve_turn.run_if(on_event::<EventTurnAsked>())
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+pub fn s_resolve_turn(
+    mut hedgehog_entities: HedgehogEntities,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_history: ResMut<ResTurnHistory>,
+    s_level_outcome: Res<State<StateLevelOutcome>>,
+    mut level_outcome_writer: LevelOutcomeWriter,
+    mut e_turn_asked: EventReader<EventTurnAsked>,
+    mut e_turn_resolved: EventWriter<EventTurnResolved>,
+) {
+    for e in e_turn_asked.read() {
+        // level already over, remaining inputs are dropped.
+        if *s_level_outcome.get() != StateLevelOutcome::Playing {
+            break
+        }
+        let snapshot = r_grid.clone();
+        let report = resolve_turn(&mut r_grid, e.direction);
+        if report.is_empty() {
+            continue
+        }
+        r_history.push_turn(snapshot, e.direction);
+        hedgehog_entities.follow_turn(&report);
+        e_turn_resolved.send(EventTurnResolved);
+        let level_outcome = get_level_outcome(&r_grid);
+        if level_outcome != StateLevelOutcome::Playing {
+            level_outcome_writer.send(level_outcome, &report, r_history.snapshots.len());
+            break
+        }
+    }
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl HedgehogEntities<'_, '_> {
+    fn follow_turn(&mut self, report: &TurnReport) {
+        for hedgehog_move in report.moves.iter() {
+            let Some(entity) = hedgehog_move.hedgehog_entity else {
+                continue
+            };
+            let Ok((mut grid_position, mut transform, _)) = self.q_hedgehogs.get_mut(entity) else {
+                continue
+            };
+            *grid_position = hedgehog_move.to;
+            *transform = get_hedgehog_transform_on_grid(&hedgehog_move.to, &self.r_hedgehog_info);
+        }
+        for hedgehog_death in report.deaths.iter() {
+            let Some(entity) = hedgehog_death.hedgehog_entity else {
+                continue
+            };
+            let Ok((_, _, material_handle)) = self.q_hedgehogs.get(entity) else {
+                continue
+            };
+            if let Some(material) = self.materials.get_mut(material_handle) {
+                material.base_color = get_hedgehog_color(hedgehog_death.cause);
+            }
+        }
+        for hedgehog_rescue in report.rescues.iter() {
+            if let Some(entity) = hedgehog_rescue.hedgehog_entity {
+                self.commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+impl LevelOutcomeWriter<'_> {
+    // Level is won through the exit its last hedgehog is rescued on.
+    // move_count: turns played, undone ones excluded.
+    fn send(&mut self, level_outcome: StateLevelOutcome, report: &TurnReport, move_count: usize) {
+        self.snext_level_outcome.set(level_outcome);
+        if let (StateLevelOutcome::Won, Some(hedgehog_rescue)) = (level_outcome, report.rescues.last()) {
+            self.e_level_won.send(
+                EventLevelWon{
+                    level_uid: self.r_current_level.level_uid,
+                    exit_position: hedgehog_rescue.position,
+                    move_count,
+                }
+            );
+        }
+    }
+}
+
+impl TurnReport {
+    // Nothing moved: turn is not worth being recorded.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty() && self.deaths.is_empty() && self.rescues.is_empty()
+    }
+}
+
+pub fn get_level_outcome(r_grid: &ResCurrentLevelGrid) -> StateLevelOutcome {
+    match PuzzleState::from_level_grid(r_grid).get_outcome() {
+        EnumPuzzleOutcome::Playing => StateLevelOutcome::Playing,
+        EnumPuzzleOutcome::Won => StateLevelOutcome::Won,
+        EnumPuzzleOutcome::Lost => StateLevelOutcome::Lost,
+    }
+}
+
+// Plays one step of the puzzle rules (see PuzzleState::step) on the current
+// level grid. Step events are replayed in order on the grid, so that every
+// hedgehog entity follows its hedgehog.
+pub fn resolve_turn(
+    r_grid: &mut ResCurrentLevelGrid,
+    direction: EnumDirection,
+) -> TurnReport {
+    let mut report = TurnReport::default();
+    let mut puzzle_state = PuzzleState::from_level_grid(r_grid);
+    let step_report = puzzle_state.step(direction);
+
+    for step_event in step_report.events {
+        match step_event {
+            EnumStepEvent::Moved{from, to} => {
+                let hedgehog = r_grid.hedgehog_grid[from.x][from.z];
+                r_grid.hedgehog_grid[from.x][from.z] = LevelGridHedgehog::default();
+                r_grid.hedgehog_grid[to.x][to.z] = hedgehog;
+                report.moves.push(
+                    HedgehogMove{
+                        hedgehog_entity: hedgehog.hedgehog_entity,
+                        from,
+                        to,
+                    }
+                );
+            },
+            EnumStepEvent::Died{position, cause} => {
+                let hedgehog = &mut r_grid.hedgehog_grid[position.x][position.z];
+                hedgehog.hedgehog_behaviour = cause;
+                report.deaths.push(
+                    HedgehogDeath{
+                        hedgehog_entity: hedgehog.hedgehog_entity,
+                        position,
+                        cause,
+                    }
+                );
+            },
+            EnumStepEvent::Rescued{position} => {
+                let hedgehog = r_grid.hedgehog_grid[position.x][position.z];
+                r_grid.hedgehog_grid[position.x][position.z] = LevelGridHedgehog::default();
+                report.rescues.push(
+                    HedgehogRescue{
+                        hedgehog_entity: hedgehog.hedgehog_entity,
+                        position,
+                    }
+                );
+            },
+        }
+    }
+    r_grid.hedgehog_saved_number = puzzle_state.hedgehog_saved_number;
+    report
+}
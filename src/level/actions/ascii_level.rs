@@ -0,0 +1,125 @@
+use uuid::Uuid;
+
+use crate::level::definition::hedgehog::{EnumTooCloseRule, HedgehogType};
+use crate::level::definition::level_definition::{LevelDescription, LevelDescriptionTile};
+use crate::level::definition::tiles::EnumTilesId;
+
+// Level description as text, one row per x, one char per z, as puzzle
+// tests write their grids. Header lines "key=value" keep uid, size and rules,
+// so that rows stripped of their trailing spaces are still read back whole.
+// Tile orientations are not written: levels read back are all Deg0.
+
+// -- CONST ------------------------------------------------------------------
+
+const ASCII_EMPTY: char = ' ';
+const ASCII_HEDGEHOG: char = 'h';
+const ASCII_TILES: [(EnumTilesId, char); 10] = [
+    (EnumTilesId::TileIdFloor, '.'),
+    (EnumTilesId::TileIdFire, 'F'),
+    (EnumTilesId::TileIdWater, 'W'),
+    (EnumTilesId::TileIdExit, 'E'),
+    (EnumTilesId::TileIdWall, '#'),
+    (EnumTilesId::TileIdWallCorner, '+'),
+    (EnumTilesId::TileIdArmoire, 'a'),
+    (EnumTilesId::TileIdTable1, '1'),
+    (EnumTilesId::TileIdTable2, '2'),
+    (EnumTilesId::TileIdDesk, 'd'),
+];
+
+const ASCII_KEY_UUID: &str = "uuid";
+const ASCII_KEY_SIZE: &str = "size";
+const ASCII_KEY_TOO_CLOSE_RULE: &str = "too_close_rule";
+
+// -- FUNCS ------------------------------------------------------------------
+
+// A hedgehog hides the tile it stands on: only floor is read back under it.
+pub fn level_description_to_ascii(level_description: &LevelDescription) -> String {
+    let mut ascii = format!(
+        "{ASCII_KEY_UUID}={}\n{ASCII_KEY_SIZE}={}x{}\n{ASCII_KEY_TOO_CLOSE_RULE}={:?}\n",
+        level_description.uuid,
+        level_description.width,
+        level_description.depth,
+        level_description.too_close_rule,
+    );
+    for row in level_description.level_grid.iter() {
+        ascii.extend(row.iter().map(get_ascii_char));
+        ascii.push('\n');
+    }
+    ascii
+}
+
+// Without size header, size is the one of the rows. Rows may be shorter than
+// the level depth: missing chars are empty tiles.
+pub fn level_description_from_ascii(ascii: &str) -> Result<LevelDescription, String> {
+    let mut uuid = Uuid::new_v4();
+    let mut size: Option<(usize, usize)> = None;
+    let mut too_close_rule = EnumTooCloseRule::default();
+    let mut rows: Vec<&str> = Vec::new();
+    for line in ascii.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            rows.push(line);
+            continue
+        };
+        match key.trim() {
+            ASCII_KEY_UUID => {
+                uuid = Uuid::parse_str(value.trim()).map_err(|error| format!("uuid: {error}"))?;
+            },
+            ASCII_KEY_SIZE => {
+                size = Some(parse_size(value).ok_or(format!("size {value} is not WIDTHxDEPTH"))?);
+            },
+            ASCII_KEY_TOO_CLOSE_RULE => {
+                too_close_rule = match value.trim() {
+                    "Stuck" => EnumTooCloseRule::Stuck,
+                    "Die" => EnumTooCloseRule::Die,
+                    value => return Err(format!("unknown too close rule {value}")),
+                };
+            },
+            key => return Err(format!("unknown header {key}")),
+        }
+    }
+    let (width, depth) = size.unwrap_or((
+        rows.len(),
+        rows.iter().map(|row| row.chars().count()).max().unwrap_or(0),
+    ));
+    if rows.len() > width || rows.iter().any(|row| row.trim_end().chars().count() > depth) {
+        return Err(format!("rows do not fit in level size {width}x{depth}"))
+    }
+    let mut level_description = LevelDescription::new(uuid, width, depth);
+    level_description.too_close_rule = too_close_rule;
+    for (x, row) in rows.iter().enumerate() {
+        for (z, c) in row.trim_end().chars().enumerate() {
+            level_description.level_grid[x][z] = get_description_tile(c)
+                .ok_or(format!("unknown tile '{c}' at [{x}][{z}]"))?;
+        }
+    }
+    Ok(level_description)
+}
+
+fn parse_size(value: &str) -> Option<(usize, usize)> {
+    let (width, depth) = value.trim().split_once('x')?;
+    Some((width.parse().ok()?, depth.parse().ok()?))
+}
+
+fn get_ascii_char(description_tile: &LevelDescriptionTile) -> char {
+    if description_tile.hedgehog.is_some() {
+        return ASCII_HEDGEHOG
+    }
+    let Some(tile_id) = description_tile.tile else {
+        return ASCII_EMPTY
+    };
+    ASCII_TILES.iter()
+        .find(|(i_tile_id, _)| *i_tile_id == tile_id)
+        .map_or(ASCII_EMPTY, |(_, c)| *c)
+}
+
+fn get_description_tile(c: char) -> Option<LevelDescriptionTile> {
+    let (tile, hedgehog) = match c {
+        ASCII_EMPTY => (None, None),
+        ASCII_HEDGEHOG => (Some(EnumTilesId::TileIdFloor), Some(HedgehogType::HedegehogeTypeStandard)),
+        c => {
+            let (tile_id, _) = ASCII_TILES.iter().find(|(_, i_c)| *i_c == c)?;
+            (Some(*tile_id), None)
+        }
+    };
+    Some(LevelDescriptionTile{tile, hedgehog, ..Default::default()})
+}
@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::level::definition::level_definition::{
@@ -6,22 +7,34 @@ use crate::level::definition::level_definition::{
     LevelGridHedgehog,
     LevelGridTile,
     ResCurrentLevel,
-    LevelUid
+    LevelUid,
+    new_grid,
 };
 use crate::level::definition::hedgehog::{
     EnumHedgehogOnGrid,
     BundleHedgehog,
     MarkerHedgehogOnLevel,
     HedgehogType,
+    ResHedgeHogInfo,
+    get_hedgehog_color,
+    get_hedgehog_transform_on_grid,
 };
 use crate::asset::asset_loader::HedgehogAssets;
 use crate::level::definition::tiles::{
     EnumeTileBehaviour,
+    EnumTileOrientation,
     ResCollectionTile,
     BundleTile,
-    MarkerTileOnLevel
+    MarkerTileOnLevel,
+    get_tile_transform_on_grid,
 
 };
+use crate::level::actions::load_run_level::despawn_current_level;
+use crate::level::actions::edit_level_history::{
+    EnumLevelEdit,
+    ResLevelEditHistory,
+    TileEditState,
+};
 
 #[derive(Event)]
 pub struct EventTileCreationAsked{
@@ -56,10 +69,29 @@ enum TileValidationPayload{
     RemovedTile,
 }
 
+// step_idx: undo step of the edit asking validation, its cascaded edits
+// join it. Several edits may be asked in one frame (level loading).
 #[derive(Event)]
 struct EventTileValidationAsked{
     pub grid_position: GridPosition,
     pub validation_payload: TileValidationPayload,
+    pub step_idx: usize,
+}
+
+// Edits asked, then validated: steps of edit history are complete after.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SSetEditLevel;
+
+// Everything tiles and hedgehogs of the current level are spawned with.
+#[derive(SystemParam)]
+pub struct LevelSpawner<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+    pub r_hedgehog: Res<'w, HedgehogAssets>,
+    pub r_hedgehog_info: Res<'w, ResHedgeHogInfo>,
+    pub r_collection_tile: Res<'w, ResCollectionTile>,
+    pub r_current_level: Res<'w, ResCurrentLevel>,
 }
 
 pub struct PluginEditLevel;
@@ -73,6 +105,8 @@ impl Plugin for PluginEditLevel{
             .add_event::<EventHedgehogRemovalAsked>()
             .add_event::<EventTileValidationAsked>()
             .add_event::<EventLevelEdidted>()
+            // chained: tiles asked in the same frame as their hedgehogs
+            // (level loading) are on the grid when hedgehogs are placed.
             .add_systems(
                 Update,
                 (
@@ -81,14 +115,175 @@ impl Plugin for PluginEditLevel{
                     s_create_hedgehog.run_if(on_event::<EventHedgehogCreationAsked>()),
                     s_remove_hedgehog.run_if(on_event::<EventHedgehogRemovalAsked>()),
                     s_validate_level_edition.run_if(on_event::<EventTileValidationAsked>())
-                )
+                ).chain().in_set(SSetEditLevel)
             );
     }
 }
 
 // -- FUNCTIONS --------------------------------------------------------------
 
-fn remove_hedgehog(
+// Spawns hedgehog entity only, grid is to be filled by caller.
+pub fn spawn_hedgehog(
+    spawner: &mut LevelSpawner,
+    hedgehog_transform: Transform,
+    grid_position: GridPosition,
+    hedgehog_behaviour: EnumHedgehogOnGrid,
+) -> Entity {
+    let hedgehog_material = spawner.materials.add(
+        StandardMaterial{
+            base_color: get_hedgehog_color(hedgehog_behaviour),
+            base_color_texture: Some(spawner.r_hedgehog.sprite_idle.clone()),
+            alpha_mode: AlphaMode::Mask(0.5),
+            ..Default::default()
+        }
+    );
+
+    let entity_commands = spawner.commands.spawn(
+        (
+            BundleHedgehog {
+                model: PbrBundle {
+                    mesh: spawner.meshes.add(Mesh::from(Plane3d{normal: Dir3::Y, half_size: Vec2{x:1.0, y:1.0,}})),
+                    material: hedgehog_material,
+                    transform: hedgehog_transform,
+                    ..Default::default()
+                },
+                grid_position,
+                hedgehog_type: HedgehogType::HedegehogeTypeStandard,
+            }, 
+            MarkerHedgehogOnLevel,
+            LevelUid{uid: spawner.r_current_level.level_uid},
+        )
+    );
+    entity_commands.id()
+}
+
+// Spawns hedgehog and fills grid, replacing any hedgehog already there.
+pub fn place_hedgehog(
+    spawner: &mut LevelSpawner,
+    r_grid: &mut ResMut<ResCurrentLevelGrid>,
+    hedgehog_transform: Transform,
+    grid_position: GridPosition,
+) {
+    let x = grid_position.x;
+    let z = grid_position.z;
+
+    remove_hedgehog(&mut spawner.commands, r_grid, x, z);
+
+    let entity = spawn_hedgehog(
+        spawner,
+        hedgehog_transform,
+        grid_position,
+        EnumHedgehogOnGrid::HedgehogAlive,
+    );
+
+    r_grid.hedgehog_grid[x][z] = LevelGridHedgehog{
+        hedgehog_behaviour: EnumHedgehogOnGrid::HedgehogAlive,
+        hedgehog_entity: Some(entity),
+        hedgehog_tile: Some(HedgehogType::HedegehogeTypeStandard),
+    };
+}
+
+// Spawns tile and fills grid, replacing any tile already there.
+pub fn place_tile(
+    commands: &mut Commands,
+    r_collection_tile: &ResCollectionTile,
+    r_current_level: &ResCurrentLevel,
+    r_grid: &mut ResMut<ResCurrentLevelGrid>,
+    tile_idx: usize,
+    tile_transform: Transform,
+    grid_position: GridPosition,
+) {
+    let tile = &r_collection_tile.tiles[tile_idx];
+    let x = grid_position.x;
+    let z = grid_position.z;
+
+    remove_tile(commands, r_grid, x, z);
+
+    let entity_commands = commands.spawn(
+        (
+            BundleTile{
+                model: SceneBundle {
+                    scene: tile.tile_model.clone(),
+                    transform: tile_transform,
+                    ..default()
+                }, 
+                tile_id: tile.tile_id,
+                grid_position,
+            },
+            MarkerTileOnLevel,
+            LevelUid{uid: r_current_level.level_uid},
+        ),
+    );
+    let entity = entity_commands.id();
+
+    r_grid.level_grid[x][z] = LevelGridTile{
+        tile_id: Some(tile.tile_id),
+        tile_behaviour: tile.tile_behaviour,
+        tile_orientation: EnumTileOrientation::from_rotation(tile_transform.rotation),
+        tile_entity: Some(entity)
+    };
+}
+
+// Replaces current level by a copy of its grid taken earlier: every tile and
+// hedgehog is respawned as it was, dead hedgehogs included.
+pub fn restore_level_grid(
+    spawner: &mut LevelSpawner,
+    r_grid: &mut ResMut<ResCurrentLevelGrid>,
+    mut level_grid: ResCurrentLevelGrid,
+) {
+    despawn_current_level(&mut spawner.commands, r_grid);
+    let empty_level_grid = new_grid(level_grid.get_width(), level_grid.get_depth(), LevelGridTile::EMPTY);
+    let tiles = std::mem::replace(&mut level_grid.level_grid, empty_level_grid);
+    **r_grid = level_grid;
+    for (x, row) in tiles.iter().enumerate() {
+        for (z, tile) in row.iter().enumerate() {
+            let Some(tile_idx) = tile.tile_id.and_then(|tile_id| {
+                spawner.r_collection_tile.tiles.iter().position(|i_tile| i_tile.tile_id == tile_id)
+            }) else {
+                continue
+            };
+            let grid_position = GridPosition{x, z};
+            place_tile(
+                &mut spawner.commands,
+                &spawner.r_collection_tile,
+                &spawner.r_current_level,
+                r_grid,
+                tile_idx,
+                get_tile_transform_on_grid(&grid_position, tile.tile_orientation),
+                grid_position,
+            );
+        }
+    }
+    respawn_hedgehogs(spawner, r_grid);
+}
+
+// Spawns an entity for every hedgehog of the grid, dead ones included.
+// Entities stored in the grid are not despawned: they are expected to be
+// gone already (grid copied earlier).
+pub fn respawn_hedgehogs(
+    spawner: &mut LevelSpawner,
+    r_grid: &mut ResCurrentLevelGrid,
+) {
+    for (x, row) in r_grid.hedgehog_grid.iter_mut().enumerate() {
+        for (z, hedgehog) in row.iter_mut().enumerate() {
+            hedgehog.hedgehog_entity = None;
+            if let EnumHedgehogOnGrid::Empty = hedgehog.hedgehog_behaviour {
+                continue
+            }
+            let grid_position = GridPosition{x, z};
+            hedgehog.hedgehog_entity = Some(
+                spawn_hedgehog(
+                    spawner,
+                    get_hedgehog_transform_on_grid(&grid_position, &spawner.r_hedgehog_info),
+                    grid_position,
+                    hedgehog.hedgehog_behaviour,
+                )
+            );
+        }
+    }
+}
+
+pub fn remove_hedgehog(
     commands: &mut Commands,
     r_grid: &mut ResMut<ResCurrentLevelGrid>,
     x: usize, z: usize,
@@ -103,7 +298,7 @@ fn remove_hedgehog(
     }
 }
 
-fn remove_tile(
+pub fn remove_tile(
     commands: &mut Commands,
     r_grid: &mut ResMut<ResCurrentLevelGrid>,
     x: usize, z: usize,
@@ -113,7 +308,8 @@ fn remove_tile(
         r_grid.level_grid[x][z] = LevelGridTile{
             tile_id: None,
             tile_entity: None,
-            tile_behaviour: EnumeTileBehaviour::Empty
+            tile_behaviour: EnumeTileBehaviour::Empty,
+            tile_orientation: EnumTileOrientation::Deg0,
         };
     }
 }
@@ -127,43 +323,37 @@ fn s_create_tile(
     mut r_grid : ResMut<ResCurrentLevelGrid>,
     r_collection_tile: Res<ResCollectionTile>,
     r_current_level: Res<ResCurrentLevel>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
     mut e_event_tile_creation_asked: EventReader<EventTileCreationAsked>,
     mut e_event_tile_validation_asked: EventWriter<EventTileValidationAsked>,
 ){
     for e in e_event_tile_creation_asked.read() {
-        let tile = &r_collection_tile.tiles[e.tile_idx];
-        let x = e.grid_position.x;
-        let z = e.grid_position.z;
-
-        remove_tile(&mut commands, &mut r_grid, x, z);
-
-        let entity_commands = commands.spawn(
-            (
-                BundleTile{
-                    model: SceneBundle {
-                        scene: tile.tile_model.clone(),
-                        transform: e.tile_transform.clone(),
-                        ..default()
-                    }, 
-                    tile_id: tile.tile_id.clone(),
-                    grid_position: GridPosition{x, z}
-                },
-                MarkerTileOnLevel,
-                LevelUid{uid: r_current_level.level_uid},
-            ),
+        let before = TileEditState::from_grid_tile(&r_grid.level_grid[e.grid_position.x][e.grid_position.z]);
+
+        place_tile(
+            &mut commands,
+            &r_collection_tile,
+            &r_current_level,
+            &mut r_grid,
+            e.tile_idx,
+            e.tile_transform,
+            e.grid_position,
         );
-        let entity = entity_commands.id();
 
-        r_grid.level_grid[x][z] = LevelGridTile{
-            tile_id: Some(tile.tile_id),
-            tile_behaviour: tile.tile_behaviour,
-            tile_entity: Some(entity)
-        };
+        let step_idx = r_edit_history.start_step();
+        r_edit_history.record_edit(
+            EnumLevelEdit::Tile{
+                grid_position: e.grid_position,
+                before,
+                after: TileEditState::from_grid_tile(&r_grid.level_grid[e.grid_position.x][e.grid_position.z]),
+            }
+        );
 
         e_event_tile_validation_asked.send(
             EventTileValidationAsked{
                 grid_position: e.grid_position.clone(),
                 validation_payload: TileValidationPayload::AddedTile,
+                step_idx,
             }
         );
     }
@@ -172,6 +362,7 @@ fn s_create_tile(
 fn s_remove_tile(
     mut commands: Commands,
     mut r_grid : ResMut<ResCurrentLevelGrid>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
     mut e_event_tile_removal_asked: EventReader<EventTileRemovalAsked>,
     mut e_event_tile_validation_asked: EventWriter<EventTileValidationAsked>,
 ) {
@@ -179,13 +370,20 @@ fn s_remove_tile(
 
         let x = e.grid_position.x;
         let z = e.grid_position.z;
+        let before = TileEditState::from_grid_tile(&r_grid.level_grid[x][z]);
 
         remove_tile(&mut commands, &mut r_grid, x, z);
 
+        let step_idx = r_edit_history.start_step();
+        r_edit_history.record_edit(
+            EnumLevelEdit::Tile{grid_position: e.grid_position, before, after: None}
+        );
+
         e_event_tile_validation_asked.send(
             EventTileValidationAsked{
                 grid_position: e.grid_position.clone(),
                 validation_payload: TileValidationPayload::RemovedTile,
+                step_idx,
             }
         );
     }
@@ -194,12 +392,9 @@ fn s_remove_tile(
 // -- hedeghog --
 
 fn s_create_hedgehog(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    r_hedgehog: Res<HedgehogAssets>,
+    mut spawner: LevelSpawner,
     mut r_grid : ResMut<ResCurrentLevelGrid>,
-    r_current_level: Res<ResCurrentLevel>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
     mut e_event_hedgehog_creation_asked: EventReader<EventHedgehogCreationAsked>,
     mut e_event_tile_validation_asked: EventWriter<EventTileValidationAsked>,
 ){
@@ -207,46 +402,29 @@ fn s_create_hedgehog(
 
         let x = e.grid_position.x;
         let z = e.grid_position.z;
+        // hedgehogs only stand on floors: edit is dropped, nothing to undo.
+        if !matches!(r_grid.level_grid[x][z].tile_behaviour, EnumeTileBehaviour::TileBFloor) {
+            continue
+        }
+        let before = r_grid.hedgehog_grid[x][z].hedgehog_entity.is_some();
 
-        let hedgehog_material = materials.add(
-            StandardMaterial{
-                base_color_texture: Some(r_hedgehog.sprite_idle.clone()),
-                alpha_mode: AlphaMode::Mask(0.5),
-                ..Default::default()
-            }
+        place_hedgehog(
+            &mut spawner,
+            &mut r_grid,
+            e.hedgehog_transform,
+            e.grid_position,
         );
 
-        remove_hedgehog(&mut commands, &mut r_grid, x, z);
-
-        let entity_commands = commands.spawn(
-            (
-                BundleHedgehog {
-                    model: PbrBundle {
-                        mesh: meshes.add(Mesh::from(Plane3d{normal: Dir3::Y, half_size: Vec2{x:1.0, y:1.0,}})),
-                        material: hedgehog_material,
-                        transform: e.hedgehog_transform.clone(),
-                        ..Default::default()
-                    },
-                    grid_position: GridPosition{x, z},
-                    hedgehog_type: HedgehogType::HedegehogeTypeStandard,
-                }, 
-                MarkerHedgehogOnLevel,
-                LevelUid{uid: r_current_level.level_uid},
-            )
+        let step_idx = r_edit_history.start_step();
+        r_edit_history.record_edit(
+            EnumLevelEdit::Hedgehog{grid_position: e.grid_position, before, after: true}
         );
 
-        let entity = entity_commands.id();
-
-        r_grid.hedgehog_grid[x][z] = LevelGridHedgehog{
-            hedgehog_behaviour: EnumHedgehogOnGrid::HedgehogAlive,
-            hedgehog_entity: Some(entity),
-            hedgehog_tile: Some(HedgehogType::HedegehogeTypeStandard),
-        };
-
         e_event_tile_validation_asked.send(
             EventTileValidationAsked{
                 grid_position: e.grid_position.clone(),
                 validation_payload: TileValidationPayload::AddedHedgehog,
+                step_idx,
             }
         );
     }
@@ -255,29 +433,39 @@ fn s_create_hedgehog(
 fn s_remove_hedgehog(
     mut commands: Commands,
     mut r_grid : ResMut<ResCurrentLevelGrid>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
     mut e_event_hedgehog_removal_asked: EventReader<EventHedgehogRemovalAsked>,
     mut e_event_tile_validation_asked: EventWriter<EventTileValidationAsked>,
 ) {
     for e in e_event_hedgehog_removal_asked.read() {
         let x = e.grid_position.x;
         let z = e.grid_position.z;
+        let before = r_grid.hedgehog_grid[x][z].hedgehog_entity.is_some();
 
         remove_hedgehog(&mut commands, &mut r_grid, x, z);
 
+        let step_idx = r_edit_history.start_step();
+        r_edit_history.record_edit(
+            EnumLevelEdit::Hedgehog{grid_position: e.grid_position, before, after: false}
+        );
+
         e_event_tile_validation_asked.send(
             EventTileValidationAsked{
                 grid_position: e.grid_position.clone(),
                 validation_payload: TileValidationPayload::RemovedHedgehog,
+                step_idx,
             }
         );
     }
 }
 
+// Cascaded removals belong to the step of the edit asking validation.
 fn s_validate_level_edition(
     mut commands: Commands,
     mut e_event_tile_validation_asked: EventReader<EventTileValidationAsked>,
     mut e_event_level_edited: EventWriter<EventLevelEdidted>,
     mut r_grid : ResMut<ResCurrentLevelGrid>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
 ) {
     for e in e_event_tile_validation_asked.read(){
 
@@ -285,19 +473,21 @@ fn s_validate_level_edition(
         let z = e.grid_position.z;
         let tile_behaviour = r_grid.level_grid[x][z].tile_behaviour;
 
-        match e.validation_payload{
-            TileValidationPayload::RemovedTile => {
-                remove_hedgehog(&mut commands, &mut r_grid, x, z);
-            },
+        // hedgehogs only stand on floors.
+        let shall_remove_hedgehog = match e.validation_payload{
+            TileValidationPayload::AddedHedgehog | TileValidationPayload::RemovedHedgehog => false,
+            TileValidationPayload::RemovedTile => true,
             TileValidationPayload::AddedTile => {
-                match tile_behaviour {
-                    EnumeTileBehaviour::TileBFloor => {}
-                    _ => {
-                        remove_hedgehog(&mut commands, &mut r_grid, x, z);
-                    }
-                }
+                !matches!(tile_behaviour, EnumeTileBehaviour::TileBFloor)
             }
-            _ => {}
+        };
+        if shall_remove_hedgehog {
+            let before = r_grid.hedgehog_grid[x][z].hedgehog_entity.is_some();
+            remove_hedgehog(&mut commands, &mut r_grid, x, z);
+            r_edit_history.record_edit_in_step(
+                e.step_idx,
+                EnumLevelEdit::Hedgehog{grid_position: e.grid_position, before, after: false},
+            );
         }
     }
     e_event_level_edited.send(EventLevelEdidted);
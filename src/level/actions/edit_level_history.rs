@@ -0,0 +1,273 @@
+use bevy::prelude::*;
+
+use crate::config::{StateGlobal, StateLevelLoaded};
+use crate::level::definition::level_definition::{
+    GridPosition,
+    LevelGridTile,
+    ResCurrentLevelGrid,
+};
+use crate::level::definition::hedgehog::get_hedgehog_transform_on_grid;
+use crate::level::definition::tiles::{
+    EnumTileOrientation,
+    EnumTilesId,
+    get_tile_transform_on_grid,
+};
+use crate::level::actions::edit_level::{
+    EventLevelEdidted,
+    LevelSpawner,
+    SSetEditLevel,
+    place_hedgehog,
+    place_tile,
+    remove_hedgehog,
+    remove_tile,
+};
+
+// -- CONST / ENUM / EVENT / COMPONENT / RESSOURCE ---------------------------
+
+// Tile laid on a cell, None when the cell is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileEditState {
+    pub tile_id: EnumTilesId,
+    pub tile_orientation: EnumTileOrientation,
+}
+
+// One change of the level grid, with the state of the cell on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumLevelEdit {
+    Tile {
+        grid_position: GridPosition,
+        before: Option<TileEditState>,
+        after: Option<TileEditState>,
+    },
+    Hedgehog {
+        grid_position: GridPosition,
+        before: bool,
+        after: bool,
+    },
+}
+
+// Every change made by one asked edit, cascaded removals included.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LevelEditStep {
+    pub edits: Vec<EnumLevelEdit>,
+}
+
+// Steps oldest first. Filled by edit_level systems, so every way of editing
+// the level is recorded.
+#[derive(Resource, Debug, Default)]
+pub struct ResLevelEditHistory {
+    pub undo_steps: Vec<LevelEditStep>,
+    pub redo_steps: Vec<LevelEditStep>,
+}
+
+#[derive(Event, Debug)]
+pub struct EventLevelEditUndoAsked;
+
+#[derive(Event, Debug)]
+pub struct EventLevelEditRedoAsked;
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginEditLevelHistory;
+
+impl Plugin for PluginEditLevelHistory {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResLevelEditHistory>()
+            .add_event::<EventLevelEditUndoAsked>()
+            .add_event::<EventLevelEditRedoAsked>()
+            // level creation goes through edit_level as well: not undoable.
+            .add_systems(OnEnter(StateLevelLoaded::Loaded), s_clear_edit_history)
+            .add_systems(OnEnter(StateGlobal::Game), s_clear_edit_history)
+            .add_systems(
+                Update,
+                // steps are complete once asked edits are validated.
+                (
+                    s_undo_level_edit.run_if(on_event::<EventLevelEditUndoAsked>()),
+                    s_redo_level_edit.run_if(on_event::<EventLevelEditRedoAsked>()),
+                ).after(SSetEditLevel)
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+fn s_clear_edit_history(mut r_edit_history: ResMut<ResLevelEditHistory>) {
+    r_edit_history.undo_steps.clear();
+    r_edit_history.redo_steps.clear();
+}
+
+fn s_undo_level_edit(
+    mut spawner: LevelSpawner,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
+    mut e_level_edit_undo_asked: EventReader<EventLevelEditUndoAsked>,
+    mut e_level_edited: EventWriter<EventLevelEdidted>,
+) {
+    for _ in e_level_edit_undo_asked.read() {
+        let Some(step) = r_edit_history.pop_undo_step() else {
+            break
+        };
+        for edit in step.get_inverse().edits.iter() {
+            apply_level_edit(
+                &mut spawner,
+                &mut r_grid,
+                edit,
+            );
+        }
+        r_edit_history.redo_steps.push(step);
+    }
+    e_level_edited.send(EventLevelEdidted);
+}
+
+fn s_redo_level_edit(
+    mut spawner: LevelSpawner,
+    mut r_grid: ResMut<ResCurrentLevelGrid>,
+    mut r_edit_history: ResMut<ResLevelEditHistory>,
+    mut e_level_edit_redo_asked: EventReader<EventLevelEditRedoAsked>,
+    mut e_level_edited: EventWriter<EventLevelEdidted>,
+) {
+    for _ in e_level_edit_redo_asked.read() {
+        let Some(step) = r_edit_history.redo_steps.pop() else {
+            break
+        };
+        for edit in step.edits.iter() {
+            apply_level_edit(
+                &mut spawner,
+                &mut r_grid,
+                edit,
+            );
+        }
+        r_edit_history.undo_steps.push(step);
+    }
+    e_level_edited.send(EventLevelEdidted);
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl TileEditState {
+    pub fn from_grid_tile(grid_tile: &LevelGridTile) -> Option<Self> {
+        grid_tile.tile_entity?;
+        Some(
+            TileEditState {
+                tile_id: grid_tile.tile_id?,
+                tile_orientation: grid_tile.tile_orientation,
+            }
+        )
+    }
+}
+
+impl EnumLevelEdit {
+    pub fn get_inverse(&self) -> Self {
+        match *self {
+            EnumLevelEdit::Tile{grid_position, before, after} => {
+                EnumLevelEdit::Tile{grid_position, before: after, after: before}
+            },
+            EnumLevelEdit::Hedgehog{grid_position, before, after} => {
+                EnumLevelEdit::Hedgehog{grid_position, before: after, after: before}
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            EnumLevelEdit::Tile{before, after, ..} => before == after,
+            EnumLevelEdit::Hedgehog{before, after, ..} => before == after,
+        }
+    }
+}
+
+impl LevelEditStep {
+    // Edits undone last first.
+    pub fn get_inverse(&self) -> Self {
+        LevelEditStep {
+            edits: self.edits.iter().rev().map(|edit| edit.get_inverse()).collect(),
+        }
+    }
+}
+
+impl ResLevelEditHistory {
+    // Every asked edit starts a step, even if it ends up changing nothing:
+    // its cascaded edits must not join the previous step. Returns the step
+    // index, for its cascaded edits.
+    pub fn start_step(&mut self) -> usize {
+        if !self.undo_steps.last().is_some_and(|step| step.edits.is_empty()) {
+            self.undo_steps.push(LevelEditStep::default());
+        }
+        self.undo_steps.len() - 1
+    }
+
+    // Adds edit to the current step.
+    pub fn record_edit(&mut self, edit: EnumLevelEdit) {
+        let Some(step_idx) = self.undo_steps.len().checked_sub(1) else {
+            return
+        };
+        self.record_edit_in_step(step_idx, edit);
+    }
+
+    // A new change drops the undone steps.
+    pub fn record_edit_in_step(&mut self, step_idx: usize, edit: EnumLevelEdit) {
+        if edit.is_empty() {
+            return
+        }
+        let Some(step) = self.undo_steps.get_mut(step_idx) else {
+            return
+        };
+        step.edits.push(edit);
+        self.redo_steps.clear();
+    }
+
+    // Last step that changed something, steps without any edit are dropped.
+    pub fn pop_undo_step(&mut self) -> Option<LevelEditStep> {
+        while let Some(step) = self.undo_steps.pop() {
+            if !step.edits.is_empty() {
+                return Some(step)
+            }
+        }
+        None
+    }
+}
+
+// Sets cell to the after state of edit, without asking any validation: the
+// cascaded edits are part of the step already.
+fn apply_level_edit(
+    spawner: &mut LevelSpawner,
+    r_grid: &mut ResMut<ResCurrentLevelGrid>,
+    edit: &EnumLevelEdit,
+) {
+    match *edit {
+        EnumLevelEdit::Tile{grid_position, after, ..} => {
+            let Some(after) = after else {
+                remove_tile(&mut spawner.commands, r_grid, grid_position.x, grid_position.z);
+                return
+            };
+            let Some(tile_idx) = spawner.r_collection_tile.tiles.iter()
+                .position(|tile| tile.tile_id == after.tile_id) else {
+                warn!("Unknown tile {:?} at {:?}, not restored.", after.tile_id, grid_position);
+                return
+            };
+            place_tile(
+                &mut spawner.commands,
+                &spawner.r_collection_tile,
+                &spawner.r_current_level,
+                r_grid,
+                tile_idx,
+                get_tile_transform_on_grid(&grid_position, after.tile_orientation),
+                grid_position,
+            );
+        },
+        EnumLevelEdit::Hedgehog{grid_position, after, ..} => {
+            if !after {
+                remove_hedgehog(&mut spawner.commands, r_grid, grid_position.x, grid_position.z);
+                return
+            }
+            let hedgehog_transform = get_hedgehog_transform_on_grid(&grid_position, &spawner.r_hedgehog_info);
+            place_hedgehog(
+                spawner,
+                r_grid,
+                hedgehog_transform,
+                grid_position,
+            );
+        },
+    }
+}
@@ -1,20 +1,48 @@
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use uuid::Uuid;
 use crate::level::definition::level_definition::{
+    GridPosition,
+    LevelDescription,
     LevelGrid,
+    LevelGridHedgehog,
+    LevelGridTile,
     LevelUid,
     ResCurrentLevel,
-    ResCurrentLevelGrid
+    ResCurrentLevelGrid,
+    LEVEL_DEFAULT_SIZE,
+    new_grid,
 };
+use crate::level::definition::tiles::{EnumeTileBehaviour, ResCollectionTile, get_tile_transform_on_grid};
+use crate::level::definition::hedgehog::{ResHedgeHogInfo, get_hedgehog_transform_on_grid};
+use crate::level::actions::edit_level::{EventTileCreationAsked, EventHedgehogCreationAsked};
+
+// Number of entities asked to be created by a level description.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LevelCreationAsked {
+    pub tile_number: usize,
+    pub hedgehog_number: usize,
+}
+
+// Asks creation of tiles and hedgehogs, through the same events as the editor.
+#[derive(SystemParam)]
+pub struct LevelCreationWriter<'w> {
+    r_collection_tile: Res<'w, ResCollectionTile>,
+    r_hedgehog_info: Res<'w, ResHedgeHogInfo>,
+    e_tile_creation_asked: EventWriter<'w, EventTileCreationAsked>,
+    e_hedgehog_creation_asked: EventWriter<'w, EventHedgehogCreationAsked>,
+}
 
 pub fn create_new_level(
     commands: &mut Commands,
-) -> Uuid {
+) -> (Uuid, Entity) {
     let uid = Uuid::new_v4();
-    commands.spawn(
+    let entity_commands = commands.spawn(
         (
             LevelGrid{
+                level_grid: new_grid(LEVEL_DEFAULT_SIZE, LEVEL_DEFAULT_SIZE, LevelGridTile::EMPTY),
+                hedgehog_grid: new_grid(LEVEL_DEFAULT_SIZE, LEVEL_DEFAULT_SIZE, LevelGridHedgehog::default()),
                 ..Default::default()
             },
             LevelUid{
@@ -22,15 +50,33 @@ pub fn create_new_level(
             },
         )
     );
-    uid
+    (uid, entity_commands.id())
+}
+
+// Spawns a level not run yet, from its description: logic grid only, tiles
+// and hedgehogs are spawned when it is run.
+pub fn spawn_level_description(
+    commands: &mut Commands,
+    level_description: &LevelDescription,
+) -> Entity {
+    commands.spawn(
+        (
+            LevelGrid::from_level_description(level_description),
+            LevelUid{
+                uid: Some(level_description.uuid)
+            },
+            level_description.clone(),
+        )
+    ).id()
 }
 
+// Returns false if no level entity has that uid.
 pub fn run_level(
     level_uid : Uuid,
     r_cur_level: &mut ResMut<ResCurrentLevel>,
     r_cur_level_grid: &mut ResMut<ResCurrentLevelGrid>,
     q_levels: &Query<(Entity, &LevelUid, &LevelGrid)>,
-) {
+) -> bool {
 
     let mut entity:Option<Entity> = None;
     let mut level_grid:Option<LevelGrid> = None;
@@ -45,18 +91,39 @@ pub fn run_level(
             continue 
         }
         entity = Some(i_entity);
-        level_grid = Some(*i_level_grid);
+        level_grid = Some(i_level_grid.clone());
         is_level_found = true;
     }
 
     if !is_level_found {
-        return
+        return false
     }
 
-    r_cur_level_grid.level_grid = level_grid.unwrap().level_grid.clone();
-    r_cur_level_grid.hedgehog_grid = level_grid.unwrap().hedgehog_grid.clone();
+    let level_grid = level_grid.unwrap();
+    r_cur_level_grid.level_grid = level_grid.level_grid;
+    r_cur_level_grid.hedgehog_grid = level_grid.hedgehog_grid;
+    r_cur_level_grid.hedgehog_saved_number = 0;
+    r_cur_level_grid.too_close_rule = level_grid.too_close_rule;
     r_cur_level.level_uid = Some(level_uid);
     r_cur_level.level_entity = entity;
+    true
+}
+
+// Despawns every tile and hedgehog of the current level, grid is left as is.
+pub fn despawn_current_level(
+    commands: &mut Commands,
+    r_cur_level_grid: &ResCurrentLevelGrid,
+) {
+    for tile in r_cur_level_grid.level_grid.iter().flatten() {
+        if let Some(entity) = tile.tile_entity {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    for hedgehog in r_cur_level_grid.hedgehog_grid.iter().flatten() {
+        if let Some(entity) = hedgehog.hedgehog_entity {
+            commands.entity(entity).despawn();
+        }
+    }
 }
 
 pub fn dump_current_level_ressource_to_components(
@@ -70,9 +137,63 @@ pub fn dump_current_level_ressource_to_components(
     }
     for (entity, mut level_grid) in q_levels.iter_mut() {
         if entity != level_entity.unwrap() {
-            return;
+            continue;
         }
         level_grid.level_grid = r_cur_level_grid.level_grid.clone();
         level_grid.hedgehog_grid = r_cur_level_grid.hedgehog_grid.clone();
+        level_grid.too_close_rule = r_cur_level_grid.too_close_rule;
+    }
+}
+
+// Asks creation of every tile and hedgehog of a level description on the
+// current level.
+pub fn ask_level_description_creation(
+    level_description: &LevelDescription,
+    level_creation_writer: &mut LevelCreationWriter,
+) -> LevelCreationAsked {
+    let LevelCreationWriter{
+        r_collection_tile,
+        r_hedgehog_info,
+        e_tile_creation_asked,
+        e_hedgehog_creation_asked,
+    } = level_creation_writer;
+    let mut creation_asked = LevelCreationAsked::default();
+    for (x, row) in level_description.level_grid.iter().enumerate() {
+        for (z, description_tile) in row.iter().enumerate() {
+            let grid_position = GridPosition{x, z};
+            let Some(tile_id) = description_tile.tile else {
+                continue
+            };
+            let Some(tile_idx) = r_collection_tile.tiles.iter()
+                .position(|tile| tile.tile_id == tile_id) else {
+                warn!("Unknown tile {:?} at {:?}, skipped.", tile_id, grid_position);
+                continue
+            };
+            e_tile_creation_asked.send(
+                EventTileCreationAsked{
+                    tile_idx,
+                    tile_transform: get_tile_transform_on_grid(&grid_position, description_tile.orientation),
+                    grid_position,
+                }
+            );
+            creation_asked.tile_number += 1;
+
+            if description_tile.hedgehog.is_none() {
+                continue
+            }
+            // same rule as edition: hedgehogs only stand on floors.
+            if tile_id.get_behaviour() != EnumeTileBehaviour::TileBFloor {
+                warn!("Hedgehog not on a floor at {:?}, skipped.", grid_position);
+                continue
+            }
+            e_hedgehog_creation_asked.send(
+                EventHedgehogCreationAsked{
+                    hedgehog_transform: get_hedgehog_transform_on_grid(&grid_position, r_hedgehog_info),
+                    grid_position,
+                }
+            );
+            creation_asked.hedgehog_number += 1;
+        }
     }
+    creation_asked
 }
@@ -1,3 +1,5 @@
 pub mod edit_level;
+pub mod edit_level_history;
 pub mod serialize;
 pub mod load_run_level;
+pub mod ascii_level;
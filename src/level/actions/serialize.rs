@@ -1,19 +1,30 @@
 use crate::level::definition::level_definition::{
     LevelDescription,
     LevelDescriptionTile,
+    LevelGrid,
+    LevelGridHedgehog,
+    LevelGridTile,
     ResCurrentLevelGrid,
-    LEVEL_DEFAULT_SIZE,
     ResCurrentLevel,
 };
-use bevy::{prelude::*, tasks::IoTaskPool};
-use std::{fs::File, io::Write};
+use crate::level::definition::hedgehog::EnumTooCloseRule;
+use crate::app::builder_bevy_app::ResTypeRegister;
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::{SceneDeserializer, SceneSerializer};
+use bevy::scene::serialize_ron;
+use uuid::Uuid;
 
-// #[derive(Resource, Default)]
-// pub struct RessourceTypeRegister {
-//     type_register: AppTypeRegistry,
-// }
+pub const LEVEL_DESC_LOCATION: &str = "levels_desc";
 
-const FILE_PATH: &str = "levels_desc/test_level.ron";
+#[derive(Event)]
+pub struct EventLevelSaveAsked;
+
+// Ok: path of the written file, Err: why it was not written.
+#[derive(Event)]
+pub struct EventLevelSaved {
+    pub result: Result<String, String>,
+}
 
 // -- PLUGIN -----------------------------------------------------------------
 
@@ -21,60 +32,136 @@ pub struct PluginSerialize;
 
 impl Plugin for PluginSerialize {
     fn build(&self, app: &mut App) {
-        // app.add_systems(Update, save_to_file_level_desc);
+        app
+            .add_event::<EventLevelSaveAsked>()
+            .add_event::<EventLevelSaved>()
+            .add_systems(
+                Update,
+                s_save_level_desc_to_file.run_if(on_event::<EventLevelSaveAsked>())
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+// ResTypeRegister holds a clone of AppTypeRegistry: levels are saved with the
+// registry they are loaded with.
+fn s_save_level_desc_to_file(
+    r_type_register: Res<ResTypeRegister>,
+    r_grid: Res<ResCurrentLevelGrid>,
+    r_current_level: Res<ResCurrentLevel>,
+    mut e_level_save_asked: EventReader<EventLevelSaveAsked>,
+    mut e_level_saved: EventWriter<EventLevelSaved>,
+) {
+    e_level_save_asked.clear();
+
+    let result = match r_current_level.level_uid {
+        None => Err("current level has no uid".to_string()),
+        Some(uuid) => {
+            let level_description = get_level_description(&r_grid, uuid);
+            let file_path = format!("assets/{}", get_level_description_file_path(uuid));
+            serialize_level_description(&level_description, &r_type_register.type_register)
+                .and_then(|serialized| write_file(&file_path, &serialized))
+                .map(|_| file_path)
+        }
+    };
+    match &result {
+        Ok(path) => info!("Level saved to {path}."),
+        Err(error) => error!("Level not saved: {error}"),
+    }
+    e_level_saved.send(EventLevelSaved{result});
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Where a level saved on its own is written, relative to assets.
+pub fn get_level_description_file_path(uuid: Uuid) -> String {
+    format!("{LEVEL_DESC_LOCATION}/{uuid}.ron")
+}
+
+pub fn get_level_description(r_grid: &ResCurrentLevelGrid, uuid: Uuid) -> LevelDescription {
+    build_level_description(&r_grid.level_grid, &r_grid.hedgehog_grid, r_grid.too_close_rule, uuid)
+}
+
+// Same as get_level_description, for a level that is not the current one.
+pub fn get_level_description_from_level_grid(level_grid: &LevelGrid, uuid: Uuid) -> LevelDescription {
+    build_level_description(&level_grid.level_grid, &level_grid.hedgehog_grid, level_grid.too_close_rule, uuid)
+}
+
+fn build_level_description(
+    level_grid: &[Vec<LevelGridTile>],
+    hedgehog_grid: &[Vec<LevelGridHedgehog>],
+    too_close_rule: EnumTooCloseRule,
+    uuid: Uuid,
+) -> LevelDescription {
+    let width = level_grid.len();
+    let depth = level_grid.first().map_or(0, |row| row.len());
+    let mut level_description = LevelDescription::new(uuid, width, depth);
+    level_description.too_close_rule = too_close_rule;
+    for (x, row) in level_description.level_grid.iter_mut().enumerate() {
+        for (z, description_tile) in row.iter_mut().enumerate() {
+            *description_tile = LevelDescriptionTile {
+                tile: level_grid[x][z].tile_id,
+                hedgehog: hedgehog_grid[x][z].hedgehog_tile,
+                orientation: level_grid[x][z].tile_orientation,
+            }
+        }
     }
+    level_description
 }
 
-// fn register_type_registry(world: &mut World) {
-//     let res = world.resource::<AppTypeRegistry>().clone();
-//     let mut type_register = world.resource_mut::<RessourceTypeRegister>();
-//     type_register.type_register = res;
-// }
-
-
-// fn save_to_file_level_desc(
-//     r_type_register: Res<RessourceTypeRegister>,
-//     r_grid: Res<ResCurrentLevelGrid>,
-//     r_keyboard_input: Res<ButtonInput<KeyCode>>,
-//     r_level_uid: Res<ResCurrentLevel>
-// ) {
-//     if !r_keyboard_input.just_pressed(KeyCode::KeyX) {
-//         return;
-//     }
-//
-//     const ARRAY_INIT_VALUE: LevelDescriptionTile = LevelDescriptionTile {
-//         tile: None,
-//         hedgehog: None,
-//     };
-//     let mut grid = [[ARRAY_INIT_VALUE; LEVEL_DEFAULT_SIZE]; LEVEL_DEFAULT_SIZE];
-//     for x in 0..=LEVEL_DEFAULT_SIZE - 1 {
-//         for z in 0..=LEVEL_DEFAULT_SIZE - 1 {
-//             grid[x][z] = LevelDescriptionTile {
-//                 tile: r_grid.level_grid[x][z].tile_id,
-//                 hedgehog: r_grid.hedgehog_grid[x][z].hedgehog_tile,
-//             }
-//         }
-//     }
-//
-//     let mut scene_world = World::new();
-//     let type_registry = r_type_register.type_register.clone();
-//     scene_world.insert_resource(type_registry);
-//     scene_world.spawn(LevelDescription {
-//         level_grid: grid,
-//         uuid: r_level_uid.episode_uid.expect("prout")
-//     });
-//     let scene = DynamicScene::from_world(&scene_world);
-//
-//     // Scenes can be serialized like this:
-//     let type_registry = r_type_register.type_register.clone();
-//     let serialized_scene_ron = scene.serialize_ron(&type_registry).unwrap();
-//     #[cfg(not(target_arch = "wasm32"))]
-//     IoTaskPool::get()
-//         .spawn(async move {
-//             // Write the scene RON data to file
-//             File::create(format!("assets/{FILE_PATH}"))
-//                 .and_then(|mut file| file.write(serialized_scene_ron.as_bytes()))
-//                 .expect("Error while writing scene to file");
-//         })
-//         .detach();
-// }
+// Serializes level description as a one entity scene.
+pub fn serialize_level_description(
+    level_description: &LevelDescription,
+    type_registry: &AppTypeRegistry,
+) -> Result<String, String> {
+    let mut scene_world = World::new();
+    scene_world.insert_resource(type_registry.clone());
+    scene_world.spawn(level_description.clone());
+    let scene = DynamicScene::from_world(&scene_world);
+    serialize_ron(SceneSerializer::new(&scene, &type_registry.read())).map_err(|error| error.to_string())
+}
+
+// Reads back a file written by serialize_level_description: first
+// LevelDescription component found in the scene.
+pub fn deserialize_level_description(
+    serialized: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<LevelDescription, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(serialized, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+    let level_description = scene.entities.iter()
+        .flat_map(|entity| entity.components.iter())
+        .find_map(|component| LevelDescription::from_reflect(component.as_ref()))
+        .ok_or("no level description found".to_string())?;
+    if !level_description.is_size_consistent() {
+        return Err(format!(
+            "level grid does not match level size {}x{}",
+            level_description.width,
+            level_description.depth,
+        ))
+    }
+    Ok(level_description)
+}
+
+// path is relative to assets directory.
+pub fn load_level_description_from_file(
+    file_path: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<LevelDescription, String> {
+    let serialized = std::fs::read_to_string(format!("assets/{file_path}"))
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    deserialize_level_description(&serialized, type_registry)
+        .map_err(|error| format!("{file_path}: {error}"))
+}
+
+// Level files are small: written right away so the result can be reported.
+pub fn write_file(file_path: &str, content: &str) -> Result<(), String> {
+    let path = std::path::Path::new(file_path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    std::fs::write(path, content).map_err(|error| error.to_string())
+}
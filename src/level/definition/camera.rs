@@ -1,8 +1,10 @@
 use bevy::{prelude::*, render::camera::ScalingMode};
 use crate::level::definition::level_definition::{
+    ResCurrentLevelGrid,
     TRANSLATION_LEVEL_ORIGIN,
     TRANSLATION_DEFAULT_CAMERA_SHIFT
 };
+use crate::level::definition::tiles::TILE_SIZE;
 
 use crate::config::{
     StateGlobal,
@@ -22,6 +24,9 @@ pub struct BundleCameraInfo{
 #[derive(Component)]
 pub struct MarkerCamera;
 
+// Window is not known when framing a level: assume a common one.
+const CAMERA_FRAMING_ASPECT_RATIO: f32 = 16.0 / 9.0;
+
 #[derive(Component)]
 pub struct MarkerCameraInfoDefault;
 
@@ -31,16 +36,33 @@ pub struct EventCameraSnap{
     pub projection: OrthographicProjection
 }
 
+// Camera slides to where it stands from there, shifted by from_offset.
+// Sent after a snap, to arrive on the snapped position smoothly.
+#[derive(Event, Debug)]
+pub struct EventCameraTransition{
+    pub from_offset: Vec3,
+}
+
+#[derive(Component)]
+struct CameraTransition{
+    from: Vec3,
+    to: Vec3,
+    timer: Timer,
+}
+
+const CAMERA_TRANSITION_DURATION_S: f32 = 0.6;
+
 pub struct PluginCamera;
 
 impl Plugin for PluginCamera {
     fn build(&self, app: &mut App){
         app
             .add_event::<EventCameraSnap>()
+            .add_event::<EventCameraTransition>()
             .add_systems(Startup, s_spawn_camera)
             .add_systems(
                 OnEnter(StateLevelLoaded::Loaded), 
-                s_camera_snap_position_default
+                (s_camera_frame_level, s_camera_snap_position_default).chain()
             )
             .add_systems(
                 OnEnter(StateGlobal::Game),
@@ -53,7 +75,11 @@ impl Plugin for PluginCamera {
             )
             .add_systems(
                 Update,
-                snap_camera.run_if(on_event::<EventCameraSnap>())
+                (
+                    snap_camera.run_if(on_event::<EventCameraSnap>()),
+                    s_camera_start_transition.run_if(on_event::<EventCameraTransition>()),
+                    s_camera_transition,
+                ).chain()
             );
     }
 }
@@ -90,6 +116,24 @@ fn s_spawn_camera(mut commands: Commands) {
     );
 }
 
+// Default camera looks at the center of the level, zoomed out enough to see
+// all of it.
+fn s_camera_frame_level(
+    r_grid: Res<ResCurrentLevelGrid>,
+    mut camera_info_query: Query<
+        (&mut Transform, &mut OrthographicProjection,),
+        With <MarkerCameraInfoDefault>
+    >,
+) {
+    let (mut transform, mut projection) = camera_info_query.single_mut();
+    let (level_transform, level_projection) = get_camera_framing_level(
+        r_grid.get_width(),
+        r_grid.get_depth(),
+    );
+    *transform = level_transform;
+    *projection = level_projection;
+}
+
 pub fn s_camera_snap_position_default(
     camera_info_query: Query<
         (&Transform, &OrthographicProjection,),
@@ -107,13 +151,16 @@ pub fn s_camera_snap_position_default(
 }
 
 pub fn snap_camera(
+    mut commands: Commands,
     mut camera_query: Query<
-        (&mut Projection, &mut Transform),
+        (Entity, &mut Projection, &mut Transform),
         (With <MarkerCamera>, Without<MarkerCameraInfoDefault>)
     >,
     mut e_camera_snap: EventReader<EventCameraSnap>,
 ){
-    let (mut cam_projection, mut cam_transform) = camera_query.single_mut();
+    let (camera_entity, mut cam_projection, mut cam_transform) = camera_query.single_mut();
+    // a snap interrupts any transition.
+    commands.entity(camera_entity).remove::<CameraTransition>();
 
     // only dealing with last event. (or sould it be first? FIXME)
     let last_event = e_camera_snap.read().last().unwrap();
@@ -124,6 +171,43 @@ pub fn snap_camera(
     *cam_transform = target_transform.clone();
 }
 
+fn s_camera_start_transition(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &mut Transform), With <MarkerCamera>>,
+    mut e_camera_transition: EventReader<EventCameraTransition>,
+){
+    let Some(last_event) = e_camera_transition.read().last() else {
+        return
+    };
+    let (camera_entity, mut cam_transform) = camera_query.single_mut();
+    let to = cam_transform.translation;
+    let from = to + last_event.from_offset;
+    cam_transform.translation = from;
+    commands.entity(camera_entity).insert(
+        CameraTransition{
+            from,
+            to,
+            timer: Timer::from_seconds(CAMERA_TRANSITION_DURATION_S, TimerMode::Once),
+        }
+    );
+}
+
+fn s_camera_transition(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut camera_query: Query<(Entity, &mut Transform, &mut CameraTransition), With <MarkerCamera>>,
+){
+    for (camera_entity, mut cam_transform, mut transition) in camera_query.iter_mut() {
+        transition.timer.tick(time.delta());
+        // ease out: fast departure, slow arrival.
+        let progress = 1.0 - (1.0 - transition.timer.fraction()).powi(2);
+        cam_transform.translation = transition.from.lerp(transition.to, progress);
+        if transition.timer.finished() {
+            commands.entity(camera_entity).remove::<CameraTransition>();
+        }
+    }
+}
+
 pub fn translate_camera(cam_tranform: &mut Mut<Transform>, translation: Vec3) {
     cam_tranform.translation = cam_tranform.translation.mul_add(Vec3::ONE, translation);
 }
@@ -168,3 +252,40 @@ pub fn zoom_camera(cam_projection: &mut Mut<Projection>, mode: ZoomCameraMode) {
     **cam_projection = Projection::Orthographic(tmp_projection.clone());
 
 }
+
+// Camera transform and projection framing a width * depth level.
+pub fn get_camera_framing_level(width: usize, depth: usize) -> (Transform, OrthographicProjection) {
+    let level_center = TRANSLATION_LEVEL_ORIGIN + Vec3::new(
+        TILE_SIZE * (width as f32 - 1.0) / 2.0,
+        0.0,
+        TILE_SIZE * (depth as f32 - 1.0) / 2.0,
+    );
+    let transform = Transform::from_translation(level_center + TRANSLATION_DEFAULT_CAMERA_SHIFT)
+        .looking_at(level_center, Vec3::Y);
+
+    // level corners as seen by the camera, tiles are centered on grid positions.
+    let inverse_transform_matrix = transform.compute_matrix().inverse();
+    let half_extent = Vec3::new(
+        TILE_SIZE * width as f32 / 2.0,
+        0.0,
+        TILE_SIZE * depth as f32 / 2.0,
+    );
+    let mut vertical_size: f32 = 0.0;
+    for corner_sign in [Vec3::new(1.0, 0.0, 1.0), Vec3::new(1.0, 0.0, -1.0)] {
+        let corner = level_center + half_extent * corner_sign;
+        let local_corner = inverse_transform_matrix.transform_point3(corner);
+        vertical_size = vertical_size
+            .max(2.0 * local_corner.y.abs())
+            .max(2.0 * local_corner.x.abs() / CAMERA_FRAMING_ASPECT_RATIO);
+    }
+    let vertical_size = match ZoomLevel::get_fitting(vertical_size) {
+        Some(zoom_level) => zoom_level as i32 as f32,
+        None => vertical_size,
+    };
+
+    let projection = OrthographicProjection {
+        scaling_mode: ScalingMode::FixedVertical(vertical_size),
+        ..default()
+    };
+    (transform, projection)
+}
@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use core::f32::consts::PI;
 
-use crate::level::definition::level_definition::GridPosition;
+use crate::level::definition::level_definition::{GridPosition, LEVEL_ORIGIN};
+use crate::level::definition::tiles::TILE_SIZE;
 
 #[derive(Bundle, Default)]
 pub struct BundleHedgehog {
@@ -10,13 +11,13 @@ pub struct BundleHedgehog {
     pub hedgehog_type: HedgehogType,
 }
 
-#[derive(Component, Default, Debug, Clone, Copy)]
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EnumHedgehogOnGrid {
     #[default]
     Empty,
     HedgehogAlive,
-    // HedgehogDeadBurnt,
-    // HedgehogDeadTooClose,
+    HedgehogDeadBurnt,
+    HedgehogDeadTooClose,
 }
 
 #[derive(Component, Reflect, Default, Debug, Clone, Copy)]
@@ -25,6 +26,17 @@ pub enum HedgehogType {
     HedegehogeTypeStandard,
 }
 
+// What happens to hedgehogs getting too close to each other, set per level.
+// - Stuck: a hedgehog blocked by another one stays in place.
+// - Die: alive hedgehogs ending a turn next to each other die. A hedgehog
+//   blocked by another one ends next to it, so it dies as well.
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumTooCloseRule {
+    #[default]
+    Stuck,
+    Die,
+}
+
 #[derive(Component)]
 pub struct MarkerHedgehogOnLevel;
 
@@ -58,3 +70,28 @@ fn s_init_hedgehog_info(mut r_hedgehog_info: ResMut<ResHedgeHogInfo>) {
             z: 1.3,
         })); // positionning it on the tiles
 }
+
+// -- FUNCS ------------------------------------------------------------------
+
+// World transform of a hedgehog standing on given grid position.
+pub fn get_hedgehog_transform_on_grid(
+    grid_position: &GridPosition,
+    r_hedgehog_info: &ResHedgeHogInfo,
+) -> Transform {
+    Transform::from_translation(
+        LEVEL_ORIGIN + Vec3::new(
+            TILE_SIZE * grid_position.x as f32,
+            0.0,
+            TILE_SIZE * grid_position.z as f32,
+        )
+    ).mul_transform(r_hedgehog_info.transform_shift)
+}
+
+// Tint applied on hedgehog material depending on its state.
+pub fn get_hedgehog_color(hedgehog_behaviour: EnumHedgehogOnGrid) -> Color {
+    match hedgehog_behaviour {
+        EnumHedgehogOnGrid::HedgehogDeadBurnt => Color::srgb(0.25, 0.1, 0.05),
+        EnumHedgehogOnGrid::HedgehogDeadTooClose => Color::srgb(0.6, 0.2, 0.6),
+        _ => Color::WHITE,
+    }
+}
@@ -10,11 +10,11 @@ use crate::level::definition::hedgehog::*;
 pub const LEVEL_ORIGIN: Vec3 = Vec3::new(0.0, 0.0, 0.0);
 pub const TRANSLATION_LEVEL_ORIGIN: Vec3 = Vec3::new(0.0, 0.0, 0.0);
 pub const TRANSLATION_DEFAULT_CAMERA_SHIFT: Vec3 = Vec3::new(9.0, 12.0, 9.0);
-pub const LEVEL_DEFAULT_SIZE: usize = 10;
+pub const LEVEL_DEFAULT_SIZE: usize = 10; // width and depth of new levels.
 
 // -- COMPONENTS -------------------------------------------------------------
 
-#[derive(Component, Debug, Default, Clone, Copy)]
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct GridPosition {
     pub x : usize,
     pub z : usize,
@@ -28,6 +28,7 @@ pub struct LevelGridTile {
     pub tile_id: Option<EnumTilesId>,
     pub tile_entity: Option<Entity>,
     pub tile_behaviour: EnumeTileBehaviour,
+    pub tile_orientation: EnumTileOrientation,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -41,17 +42,24 @@ pub struct LevelGridHedgehog {
 // --- "Description" a level. ---
 // a sum up of the level that is used to serialize / deserialie levels.
 
-#[derive(Component, Reflect)]
+// level_grid is indexed [x][z]: width rows of depth tiles.
+#[derive(Component, Reflect, Default, Clone)]
 #[reflect(Component)]
 pub struct LevelDescription {
-    pub level_grid: [[LevelDescriptionTile; LEVEL_DEFAULT_SIZE];LEVEL_DEFAULT_SIZE],
-    pub uuid: Uuid
+    pub width: usize,
+    pub depth: usize,
+    pub level_grid: Vec<Vec<LevelDescriptionTile>>,
+    pub uuid: Uuid,
+    #[reflect(default)]
+    pub too_close_rule: EnumTooCloseRule,
 }
 
-#[derive(Reflect, Clone, Copy)]
+#[derive(Reflect, Default, Clone, Copy)]
 pub struct LevelDescriptionTile {
     pub tile: Option<EnumTilesId>,
     pub hedgehog: Option<HedgehogType>,
+    #[reflect(default)]
+    pub orientation: EnumTileOrientation,
     // pub object: bool // TODO: LATER.
 }
 
@@ -60,10 +68,11 @@ pub struct LevelUid{
     pub uid: Option<Uuid>,
 }
 
-#[derive(Component, Default, Debug, Clone, Copy)]
+#[derive(Component, Default, Debug, Clone)]
 pub struct LevelGrid {
-    pub level_grid: [[LevelGridTile; LEVEL_DEFAULT_SIZE];LEVEL_DEFAULT_SIZE],
-    pub hedgehog_grid: [[LevelGridHedgehog; LEVEL_DEFAULT_SIZE];LEVEL_DEFAULT_SIZE],
+    pub level_grid: Vec<Vec<LevelGridTile>>,
+    pub hedgehog_grid: Vec<Vec<LevelGridHedgehog>>,
+    pub too_close_rule: EnumTooCloseRule,
 }
 
 // -- RESSOURCES -------------------------------------------------------------
@@ -75,10 +84,138 @@ pub struct ResCurrentLevel{
     // TODO -> level grid ?
 }
 
-#[derive(Resource, Debug, Default)]
+// Grids are indexed [x][z], see ResCurrentLevelGrid::new.
+#[derive(Resource, Debug, Default, Clone)]
 pub struct ResCurrentLevelGrid {
-    pub level_grid: [[LevelGridTile; LEVEL_DEFAULT_SIZE];LEVEL_DEFAULT_SIZE],
-    pub hedgehog_grid: [[LevelGridHedgehog; LEVEL_DEFAULT_SIZE];LEVEL_DEFAULT_SIZE],
+    pub level_grid: Vec<Vec<LevelGridTile>>,
+    pub hedgehog_grid: Vec<Vec<LevelGridHedgehog>>,
+    pub hedgehog_saved_number: usize, // hedgehogs that reached an exit.
+    pub too_close_rule: EnumTooCloseRule,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// width rows (along x) of depth values (along z).
+pub fn new_grid<T: Clone>(width: usize, depth: usize, value: T) -> Vec<Vec<T>> {
+    vec![vec![value; depth]; width]
+}
+
+impl LevelGridTile {
+    pub const EMPTY: LevelGridTile = LevelGridTile {
+        tile_id: None,
+        tile_entity: None,
+        tile_behaviour: EnumeTileBehaviour::Empty,
+        tile_orientation: EnumTileOrientation::Deg0,
+    };
+}
+
+impl LevelDescription {
+    // Level without any tile nor hedgehog.
+    pub fn new(uuid: Uuid, width: usize, depth: usize) -> Self {
+        LevelDescription {
+            width,
+            depth,
+            level_grid: new_grid(width, depth, LevelDescriptionTile::default()),
+            uuid,
+            too_close_rule: EnumTooCloseRule::default(),
+        }
+    }
+
+    // Size written in the file is to be trusted only if grid agrees with it.
+    pub fn is_size_consistent(&self) -> bool {
+        self.level_grid.len() == self.width
+            && self.level_grid.iter().all(|row| row.len() == self.depth)
+    }
+
+    // What makes the level unplayable, empty if nothing does. Winning it is
+    // left to the solver.
+    pub fn get_errors(&self) -> Vec<String> {
+        if !self.is_size_consistent() {
+            return vec![format!("level grid does not match level size {}x{}", self.width, self.depth)]
+        }
+        let mut errors = Vec::new();
+        let mut hedgehog_count = 0;
+        let mut exit_count = 0;
+        for (x, row) in self.level_grid.iter().enumerate() {
+            for (z, description_tile) in row.iter().enumerate() {
+                let tile_behaviour = description_tile.tile.map(|tile_id| tile_id.get_behaviour());
+                if tile_behaviour == Some(EnumeTileBehaviour::TileBExit) {
+                    exit_count += 1;
+                }
+                if description_tile.hedgehog.is_none() {
+                    continue
+                }
+                hedgehog_count += 1;
+                if tile_behaviour != Some(EnumeTileBehaviour::TileBFloor) {
+                    errors.push(format!("hedgehog at [{x}][{z}] is not on a floor"));
+                }
+            }
+        }
+        if hedgehog_count == 0 {
+            errors.push("level has no hedgehog".to_string());
+        }
+        if exit_count == 0 {
+            errors.push("level has no exit".to_string());
+        }
+        errors
+    }
+}
+
+impl LevelGrid {
+    // Logic grid of a level not spawned yet: no entity, alive hedgehogs.
+    pub fn from_level_description(level_description: &LevelDescription) -> Self {
+        let width = level_description.width;
+        let depth = level_description.depth;
+        let mut level_grid = LevelGrid {
+            level_grid: new_grid(width, depth, LevelGridTile::EMPTY),
+            hedgehog_grid: new_grid(width, depth, LevelGridHedgehog::default()),
+            too_close_rule: level_description.too_close_rule,
+        };
+        for (x, row) in level_description.level_grid.iter().enumerate() {
+            for (z, description_tile) in row.iter().enumerate() {
+                let Some(tile_id) = description_tile.tile else {
+                    continue
+                };
+                level_grid.level_grid[x][z] = LevelGridTile {
+                    tile_id: Some(tile_id),
+                    tile_entity: None,
+                    tile_behaviour: tile_id.get_behaviour(),
+                    tile_orientation: description_tile.orientation,
+                };
+                // same rule as edition: hedgehogs only stand on floors.
+                if tile_id.get_behaviour() != EnumeTileBehaviour::TileBFloor {
+                    continue
+                }
+                if let Some(hedgehog_type) = description_tile.hedgehog {
+                    level_grid.hedgehog_grid[x][z] = LevelGridHedgehog {
+                        hedgehog_behaviour: EnumHedgehogOnGrid::HedgehogAlive,
+                        hedgehog_entity: None,
+                        hedgehog_tile: Some(hedgehog_type),
+                    };
+                }
+            }
+        }
+        level_grid
+    }
+}
+
+impl ResCurrentLevelGrid {
+    // Grid without any tile nor hedgehog.
+    pub fn new(width: usize, depth: usize) -> Self {
+        ResCurrentLevelGrid {
+            level_grid: new_grid(width, depth, LevelGridTile::EMPTY),
+            hedgehog_grid: new_grid(width, depth, LevelGridHedgehog::default()),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.level_grid.len()
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.level_grid.first().map_or(0, |row| row.len())
+    }
 }
 
 pub struct PluginLevelDefinition;
@@ -91,7 +228,9 @@ impl Plugin for PluginLevelDefinition{
             .register_type::<LevelDescription>()
             .register_type::<LevelDescriptionTile>()
             .register_type::<EnumTilesId>()
-            .register_type::<HedgehogType>();
+            .register_type::<EnumTileOrientation>()
+            .register_type::<HedgehogType>()
+            .register_type::<EnumTooCloseRule>();
 
 
     }
@@ -1,11 +1,11 @@
 use bevy::prelude::*;
-use  crate::level::definition::level_definition::GridPosition;
+use  crate::level::definition::level_definition::{GridPosition, LEVEL_ORIGIN};
 use crate::asset::asset_loader::{SceneAssets, s_load_scene_assets};
 
 pub const TILE_SIZE: f32 = 2.0;
 pub const TILE_WIDTH: f32 = 0.3;
 
-#[derive(Reflect, Component, Default, Debug, Clone, Copy)]
+#[derive(Reflect, Component, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EnumTilesId {
     #[default]
     TileIdFloor,
@@ -20,7 +20,7 @@ pub enum EnumTilesId {
     TileIdDesk,
 }
 
-#[derive(Reflect, Component, Default, Debug, Clone, Copy)]
+#[derive(Reflect, Component, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EnumeTileBehaviour {
     #[default]
     TileBFloor,
@@ -31,6 +31,58 @@ pub enum EnumeTileBehaviour {
     Empty,
 }
 
+// Rotation of a tile around y, by quarter turns: the editor "r" key.
+#[derive(Reflect, Component, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumTileOrientation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl EnumTileOrientation {
+    // Rounds rotation to the closest quarter turn around y.
+    pub fn from_rotation(rotation: Quat) -> Self {
+        let (yaw, _, _) = rotation.to_euler(EulerRot::YXZ);
+        let quarter_turns = (yaw / std::f32::consts::FRAC_PI_2).round() as i32;
+        match quarter_turns.rem_euclid(4) {
+            1 => EnumTileOrientation::Deg90,
+            2 => EnumTileOrientation::Deg180,
+            3 => EnumTileOrientation::Deg270,
+            _ => EnumTileOrientation::Deg0,
+        }
+    }
+
+    pub fn to_rotation(&self) -> Quat {
+        let quarter_turns = match self {
+            EnumTileOrientation::Deg0 => 0.0,
+            EnumTileOrientation::Deg90 => 1.0,
+            EnumTileOrientation::Deg180 => 2.0,
+            EnumTileOrientation::Deg270 => 3.0,
+        };
+        Quat::from_rotation_y(quarter_turns * std::f32::consts::FRAC_PI_2)
+    }
+}
+
+impl EnumTilesId {
+    // Gameplay behaviour of a tile, known without loading any asset.
+    pub fn get_behaviour(&self) -> EnumeTileBehaviour {
+        match self {
+            EnumTilesId::TileIdFloor => EnumeTileBehaviour::TileBFloor,
+            EnumTilesId::TileIdFire => EnumeTileBehaviour::TileBFire,
+            EnumTilesId::TileIdWater => EnumeTileBehaviour::TileBWater,
+            EnumTilesId::TileIdExit => EnumeTileBehaviour::TileBExit,
+            EnumTilesId::TileIdArmoire
+            | EnumTilesId::TileIdTable1
+            | EnumTilesId::TileIdTable2
+            | EnumTilesId::TileIdWallCorner
+            | EnumTilesId::TileIdWall
+            | EnumTilesId::TileIdDesk => EnumeTileBehaviour::TileBObstacle,
+        }
+    }
+}
+
 pub struct DefinitionTile{
     pub tile_id: EnumTilesId,
     pub tile_model: Handle<Scene>,
@@ -69,70 +121,86 @@ fn s_build_res_collection_tiles(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdFloor,
             tile_model: scene_assets.tile_floor.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBFloor,
+            tile_behaviour: EnumTilesId::TileIdFloor.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdFire,
             tile_model: scene_assets.tile_fire.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBFire,
+            tile_behaviour: EnumTilesId::TileIdFire.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdWater,
             tile_model: scene_assets.tile_water.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBWater,
+            tile_behaviour: EnumTilesId::TileIdWater.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdExit,
             tile_model: scene_assets.tile_exit.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBExit,
+            tile_behaviour: EnumTilesId::TileIdExit.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdArmoire,
             tile_model: scene_assets.tile_armoire.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdArmoire.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdTable1,
             tile_model: scene_assets.tile_table_1.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdTable1.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdTable2,
             tile_model: scene_assets.tile_table_2.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdTable2.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdWallCorner,
             tile_model: scene_assets.tile_wall_corner.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdWallCorner.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdWall,
             tile_model: scene_assets.tile_wall.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdWall.get_behaviour(),
         }
     );
     res_collection_tiles.tiles.push(
         DefinitionTile {
             tile_id: EnumTilesId::TileIdDesk,
             tile_model: scene_assets.title_desk.clone(),
-            tile_behaviour: EnumeTileBehaviour::TileBObstacle,
+            tile_behaviour: EnumTilesId::TileIdDesk.get_behaviour(),
         }
     );
 }
+
+// -- FUNCS ------------------------------------------------------------------
+
+// World transform of a tile laid on given grid position.
+pub fn get_tile_transform_on_grid(
+    grid_position: &GridPosition,
+    tile_orientation: EnumTileOrientation,
+) -> Transform {
+    Transform::from_translation(
+        LEVEL_ORIGIN + Vec3::new(
+            TILE_SIZE * grid_position.x as f32,
+            0.0,
+            TILE_SIZE * grid_position.z as f32,
+        )
+    ).with_rotation(tile_orientation.to_rotation())
+}
@@ -1,4 +1,5 @@
 
+#[derive(Clone, Copy)]
 pub enum ZoomLevel {
     REALLYSMALL = 6,
     SMALL = 10,
@@ -28,6 +29,19 @@ impl ZoomLevel {
         }
     }
 
+    // Smallest zoom level showing at least vertical_size, None if too big.
+    pub fn get_fitting(vertical_size: f32) -> Option<ZoomLevel> {
+        [
+            ZoomLevel::REALLYSMALL,
+            ZoomLevel::SMALL,
+            ZoomLevel::NORMAL,
+            ZoomLevel::BIG,
+            ZoomLevel::REALLYBIG,
+        ]
+        .into_iter()
+        .find(|zoom_level| *zoom_level as i32 as f32 >= vertical_size)
+    }
+
     pub fn get_from_i32(value: i32) -> Option<ZoomLevel> {
 
         const I32_REALLYSMALL: i32 = ZoomLevel::REALLYSMALL as i32;
@@ -1,3 +1,4 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use uuid::Uuid;
 
@@ -5,11 +6,19 @@ use crate::config::*;
 use crate::level::definition::camera::*;
 use crate::level::definition::hedgehog::*;
 use crate::level::definition::level_definition::*;
-use crate::level::definition::level_definition::{ResCurrentLevelGrid, LevelGridTile};
+use crate::level::definition::level_definition::ResCurrentLevelGrid;
 use crate::level::definition::tiles::*;
 
 use crate::level::actions::edit_level::*;
+use crate::level::actions::edit_level_history::PluginEditLevelHistory;
 use crate::level::actions::serialize::*;
+use crate::level::actions::load_run_level::{
+    ask_level_description_creation,
+    despawn_current_level,
+    run_level,
+    LevelCreationAsked,
+    LevelCreationWriter,
+};
 
 // CONST / ENUM / EVENT / COMPONENT / RESSOURCE ------------------------------
 
@@ -17,6 +26,33 @@ use crate::level::actions::serialize::*;
 #[derive(Component)]
 pub struct MarkerTextLoadingLevel;
 
+// Level loaded when entering StateLevelLoaded::Loading:
+// - level_uid: level entity of the current episode, run through run_level.
+// - level_path: level description file (relative to assets).
+// None for both: starts on a new empty level.
+#[derive(Resource, Default)]
+pub struct ResLevelToLoad {
+    pub level_uid: Option<Uuid>,
+    pub level_path: Option<String>,
+}
+
+// What the loading level waits for before being "Loaded".
+#[derive(Resource, Default)]
+struct ResLevelLoadingProgress {
+    creation_asked: LevelCreationAsked,
+}
+
+// Leaves the current level for the level to load, user inputs waiting for
+// it to be loaded.
+#[derive(SystemParam)]
+pub struct LevelSwitcher<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub r_grid: Res<'w, ResCurrentLevelGrid>,
+    pub r_level_to_load: ResMut<'w, ResLevelToLoad>,
+    snext_level_loaded: ResMut<'w, NextState<StateLevelLoaded>>,
+    snext_user_input_allowed: ResMut<'w, NextState<StateUserInputAllowed>>,
+}
+
 // TODO (roadmap, no neorg)
 // levelInfo (name / id / size) for every level.
 // but also dynamic scene for dynmaic scene.
@@ -37,14 +73,21 @@ impl Plugin for PluginLevel {
             .add_plugins(PluginTiles)
             .add_plugins(PluginLevelDefinition)
             .add_plugins(PluginEditLevel)
+            .add_plugins(PluginEditLevelHistory)
             .add_plugins(PluginSerialize)
             .insert_resource(ResCurrentLevelGrid::default())
+            .init_resource::<ResLevelToLoad>()
+            .init_resource::<ResLevelLoadingProgress>()
             // TODO: tmp...
             .add_systems(
                 PostStartup,
                 s_level_loading_prepare.run_if(in_state(StateLevelLoaded::NotLoaded)),
             )
-            .add_systems(OnEnter(StateLevelLoaded::Loading), s_level_loading_load);
+            .add_systems(OnEnter(StateLevelLoaded::Loading), s_level_loading_load)
+            .add_systems(
+                Update,
+                s_level_loading_wait.run_if(in_state(StateLevelLoaded::Loading))
+            );
     }
 }
 
@@ -81,26 +124,87 @@ fn s_level_loading_prepare(
 }
 
 fn s_level_loading_load(
-    mut commands: Commands,
-    mut s_level_loaded: ResMut<NextState<StateLevelLoaded>>,
     mut r_level_grid: ResMut<ResCurrentLevelGrid>,
     mut r_current_level: ResMut<ResCurrentLevel>,
-    mut s_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
-    entity: Query<Entity, With<MarkerTextLoadingLevel>>,
+    mut r_loading_progress: ResMut<ResLevelLoadingProgress>,
+    r_level_to_load: Res<ResLevelToLoad>,
+    r_type_registry: Res<AppTypeRegistry>,
+    q_levels: Query<(Entity, &LevelUid, &LevelGrid)>,
+    mut level_creation_writer: LevelCreationWriter,
 ) {
-    // const ARRAY_REPEAT_VALUE:EnumeTileBehaviour = EnumeTileBehaviour::Empty;
-    // FIXME: WAIT WILL IT BE THE SAME REFERENCE?
-    const ARRAY_REPEAT_VALUE: LevelGridTile = LevelGridTile {
-        tile_behaviour: EnumeTileBehaviour::Empty,
-        tile_id: None,
-        tile_entity: None,
+    let level_description = match (r_level_to_load.level_uid, &r_level_to_load.level_path) {
+        (Some(level_uid), _) => {
+            if run_level(level_uid, &mut r_current_level, &mut r_level_grid, &q_levels) {
+                Some(get_level_description(&r_level_grid, level_uid))
+            } else {
+                error!("Level {level_uid} not found, starting on an empty level.");
+                None
+            }
+        },
+        (None, Some(level_path)) => {
+            r_current_level.level_entity = None;
+            match load_level_description_from_file(level_path, &r_type_registry) {
+                Ok(level_description) => Some(level_description),
+                Err(error) => {
+                    error!("Level not loaded, starting on an empty level: {error}");
+                    None
+                }
+            }
+        },
+        (None, None) => None,
+    };
+
+    let Some(level_description) = level_description else {
+        *r_level_grid = ResCurrentLevelGrid::new(LEVEL_DEFAULT_SIZE, LEVEL_DEFAULT_SIZE);
+        r_current_level.level_uid = Some(Uuid::new_v4());
+        r_current_level.level_entity = None;
+        r_loading_progress.creation_asked = LevelCreationAsked::default();
+        return
     };
-    r_level_grid.level_grid = [[ARRAY_REPEAT_VALUE; LEVEL_DEFAULT_SIZE]; LEVEL_DEFAULT_SIZE];
-    commands.entity(entity.single()).despawn();
+
+    // set before asking creation: entities are tagged with current level uid.
+    *r_level_grid = ResCurrentLevelGrid::new(level_description.width, level_description.depth);
+    r_level_grid.too_close_rule = level_description.too_close_rule;
+    r_current_level.level_uid = Some(level_description.uuid);
+    r_loading_progress.creation_asked = ask_level_description_creation(
+        &level_description,
+        &mut level_creation_writer,
+    );
+}
+
+// Level is loaded once every asked tile / hedgehog is on the grid.
+fn s_level_loading_wait(
+    mut commands: Commands,
+    r_level_grid: Res<ResCurrentLevelGrid>,
+    r_loading_progress: Res<ResLevelLoadingProgress>,
+    mut s_level_loaded: ResMut<NextState<StateLevelLoaded>>,
+    mut s_user_input_allowed: ResMut<NextState<StateUserInputAllowed>>,
+    q_text_loading_level: Query<Entity, With<MarkerTextLoadingLevel>>,
+) {
+    let tile_number = r_level_grid.level_grid.iter().flatten()
+        .filter(|tile| tile.tile_entity.is_some())
+        .count();
+    let hedgehog_number = r_level_grid.hedgehog_grid.iter().flatten()
+        .filter(|hedgehog| hedgehog.hedgehog_entity.is_some())
+        .count();
+    let creation_asked = &r_loading_progress.creation_asked;
+    if tile_number < creation_asked.tile_number || hedgehog_number < creation_asked.hedgehog_number {
+        return
+    }
+    if let Ok(entity) = q_text_loading_level.get_single() {
+        commands.entity(entity).despawn();
+    }
     s_level_loaded.set(StateLevelLoaded::Loaded);
     s_user_input_allowed.set(StateUserInputAllowed::Allowed);
-    r_current_level.level_uid = Some(Uuid::new_v4());
 }
 
 // -- FUNCS ------------------------------------------------------------------
 
+impl LevelSwitcher<'_, '_> {
+    pub fn switch_level(&mut self) {
+        despawn_current_level(&mut self.commands, &self.r_grid);
+        self.snext_user_input_allowed.set(StateUserInputAllowed::NotAllowed);
+        self.snext_level_loaded.set(StateLevelLoaded::Loading);
+    }
+}
+
@@ -4,4 +4,6 @@ pub mod editor;
 pub mod episode;
 pub mod game;
 pub mod level;
+pub mod puzzle;
+pub mod suite;
 pub mod app;
@@ -0,0 +1,3 @@
+pub mod puzzle_state;
+pub mod solver;
+pub mod path_trace;
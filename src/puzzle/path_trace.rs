@@ -0,0 +1,73 @@
+use crate::level::definition::hedgehog::EnumHedgehogOnGrid;
+use crate::level::definition::level_definition::GridPosition;
+use crate::puzzle::puzzle_state::{EnumDirection, EnumStepEvent, PuzzleState};
+
+// How many times hedgehogs stepped on every cell while playing some moves:
+// cells nobody steps on are fluff the level can do without.
+
+// -- STRUCT -----------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTrace {
+    pub width: usize, // along x
+    pub depth: usize, // along z
+    pub step_counts: Vec<usize>,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl PathTrace {
+    pub fn new(width: usize, depth: usize) -> Self {
+        PathTrace {
+            width,
+            depth,
+            step_counts: vec![0; width * depth],
+        }
+    }
+
+    // Plays moves on a copy of state. Cells hedgehogs start on count as
+    // stepped on, as well as water cells slid over.
+    pub fn from_moves(state: &PuzzleState, moves: &[EnumDirection]) -> Self {
+        let mut trace = PathTrace::new(state.width, state.depth);
+        let mut state = state.clone();
+        for position in state.iter_positions() {
+            if state.get_hedgehog(&position) == EnumHedgehogOnGrid::HedgehogAlive {
+                trace.add_step(&position);
+            }
+        }
+        for direction in moves {
+            let report = state.step(*direction);
+            for event in report.events.iter() {
+                let EnumStepEvent::Moved{from, to} = event else {
+                    continue
+                };
+                let mut position = *from;
+                while position != *to {
+                    let Some(next) = state.get_neighbour(&position, *direction) else {
+                        break
+                    };
+                    trace.add_step(&next);
+                    position = next;
+                }
+            }
+        }
+        trace
+    }
+
+    fn get_idx(&self, position: &GridPosition) -> usize {
+        position.x * self.depth + position.z
+    }
+
+    fn add_step(&mut self, position: &GridPosition) {
+        let idx = self.get_idx(position);
+        self.step_counts[idx] += 1;
+    }
+
+    pub fn get_step_count(&self, position: &GridPosition) -> usize {
+        self.step_counts[self.get_idx(position)]
+    }
+
+    pub fn get_max_step_count(&self) -> usize {
+        self.step_counts.iter().copied().max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,310 @@
+use crate::level::definition::level_definition::{
+    GridPosition,
+    LevelDescription,
+    ResCurrentLevelGrid,
+};
+use crate::level::definition::hedgehog::{EnumHedgehogOnGrid, EnumTooCloseRule};
+use crate::level::definition::tiles::EnumeTileBehaviour;
+
+// Headless representation of a level and its rules.
+// Knows nothing about entities, meshes or scenes: game systems drive it and
+// mirror its step reports on entities, editor / solver / tests use it as is.
+
+// -- CONST / ENUM -----------------------------------------------------------
+
+// Grid is seen from (+x, +z) corner, so "up" on screen goes toward -x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumDirection {
+    Up,    // -x
+    Down,  // +x
+    Left,  // +z
+    Right, // -z
+}
+
+pub const DIRECTIONS: [EnumDirection; 4] = [
+    EnumDirection::Up,
+    EnumDirection::Down,
+    EnumDirection::Left,
+    EnumDirection::Right,
+];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnumPuzzleOutcome {
+    #[default]
+    Playing,
+    Won,
+    Lost,
+}
+
+// Events are listed in the order they were resolved: replaying them in order
+// on another grid (holding entities for instance) gives the same result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStepEvent {
+    Moved { from: GridPosition, to: GridPosition },
+    Died { position: GridPosition, cause: EnumHedgehogOnGrid },
+    Rescued { position: GridPosition },
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StepReport {
+    pub events: Vec<EnumStepEvent>,
+}
+
+// -- STRUCT -----------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PuzzleState {
+    pub width: usize, // along x
+    pub depth: usize, // along z
+    pub tiles: Vec<EnumeTileBehaviour>,
+    pub hedgehogs: Vec<EnumHedgehogOnGrid>,
+    pub hedgehog_saved_number: usize,
+    pub too_close_rule: EnumTooCloseRule,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl StepReport {
+    // Nothing happened: step is not worth being recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+impl EnumDirection {
+    // How far a position is along the direction: hedgehogs in front move first.
+    fn progress(&self, position: &GridPosition) -> i64 {
+        let (x, z) = (position.x as i64, position.z as i64);
+        match self {
+            EnumDirection::Up => -x,
+            EnumDirection::Down => x,
+            EnumDirection::Left => z,
+            EnumDirection::Right => -z,
+        }
+    }
+}
+
+pub fn is_tile_walkable(tile_behaviour: EnumeTileBehaviour) -> bool {
+    match tile_behaviour {
+        EnumeTileBehaviour::TileBFloor
+        | EnumeTileBehaviour::TileBFire
+        | EnumeTileBehaviour::TileBWater
+        | EnumeTileBehaviour::TileBExit => true,
+        EnumeTileBehaviour::TileBObstacle
+        | EnumeTileBehaviour::Empty => false,
+    }
+}
+
+impl PuzzleState {
+    pub fn new(width: usize, depth: usize) -> Self {
+        PuzzleState {
+            width,
+            depth,
+            tiles: vec![EnumeTileBehaviour::Empty; width * depth],
+            hedgehogs: vec![EnumHedgehogOnGrid::Empty; width * depth],
+            hedgehog_saved_number: 0,
+            too_close_rule: EnumTooCloseRule::default(),
+        }
+    }
+
+    pub fn from_level_description(level_description: &LevelDescription) -> Self {
+        let width = level_description.level_grid.len();
+        let depth = level_description.level_grid.first().map_or(0, |row| row.len());
+        let mut state = PuzzleState::new(width, depth);
+        state.too_close_rule = level_description.too_close_rule;
+        for (x, row) in level_description.level_grid.iter().enumerate() {
+            for (z, tile) in row.iter().enumerate() {
+                let position = GridPosition{x, z};
+                if let Some(tile_id) = tile.tile {
+                    state.set_tile(&position, tile_id.get_behaviour());
+                }
+                if tile.hedgehog.is_some() {
+                    state.set_hedgehog(&position, EnumHedgehogOnGrid::HedgehogAlive);
+                }
+            }
+        }
+        state
+    }
+
+    pub fn from_level_grid(r_grid: &ResCurrentLevelGrid) -> Self {
+        let width = r_grid.level_grid.len();
+        let depth = r_grid.level_grid.first().map_or(0, |row| row.len());
+        let mut state = PuzzleState::new(width, depth);
+        state.too_close_rule = r_grid.too_close_rule;
+        state.hedgehog_saved_number = r_grid.hedgehog_saved_number;
+        for x in 0..width {
+            for z in 0..depth {
+                let position = GridPosition{x, z};
+                state.set_tile(&position, r_grid.level_grid[x][z].tile_behaviour);
+                state.set_hedgehog(&position, r_grid.hedgehog_grid[x][z].hedgehog_behaviour);
+            }
+        }
+        state
+    }
+
+    fn get_idx(&self, position: &GridPosition) -> usize {
+        position.x * self.depth + position.z
+    }
+
+    pub fn get_tile(&self, position: &GridPosition) -> EnumeTileBehaviour {
+        self.tiles[self.get_idx(position)]
+    }
+
+    pub fn set_tile(&mut self, position: &GridPosition, tile_behaviour: EnumeTileBehaviour) {
+        let idx = self.get_idx(position);
+        self.tiles[idx] = tile_behaviour;
+    }
+
+    pub fn get_hedgehog(&self, position: &GridPosition) -> EnumHedgehogOnGrid {
+        self.hedgehogs[self.get_idx(position)]
+    }
+
+    pub fn set_hedgehog(&mut self, position: &GridPosition, hedgehog: EnumHedgehogOnGrid) {
+        let idx = self.get_idx(position);
+        self.hedgehogs[idx] = hedgehog;
+    }
+
+    fn is_hedgehog_alive(&self, position: &GridPosition) -> bool {
+        matches!(self.get_hedgehog(position), EnumHedgehogOnGrid::HedgehogAlive)
+    }
+
+    // Every position of the grid, x major.
+    pub fn iter_positions(&self) -> impl Iterator<Item = GridPosition> {
+        let depth = self.depth;
+        (0..self.width).flat_map(move |x| (0..depth).map(move |z| GridPosition{x, z}))
+    }
+
+    // Returns the neighbour cell in that direction, None if out of the grid.
+    pub fn get_neighbour(
+        &self,
+        position: &GridPosition,
+        direction: EnumDirection,
+    ) -> Option<GridPosition> {
+        let (x, z) = (position.x, position.z);
+        let (x, z) = match direction {
+            EnumDirection::Up => (x.checked_sub(1)?, z),
+            EnumDirection::Down => (x + 1, z),
+            EnumDirection::Left => (x, z + 1),
+            EnumDirection::Right => (x, z.checked_sub(1)?),
+        };
+        if x >= self.width || z >= self.depth {
+            return None
+        }
+        Some(GridPosition{x, z})
+    }
+
+    // Returns the next cell a hedgehog can step on, None if it is blocked.
+    fn get_next_free_position(
+        &self,
+        position: &GridPosition,
+        direction: EnumDirection,
+    ) -> Option<GridPosition> {
+        let next = self.get_neighbour(position, direction)?;
+        if !is_tile_walkable(self.get_tile(&next)) {
+            return None
+        }
+        match self.get_hedgehog(&next) {
+            EnumHedgehogOnGrid::Empty => Some(next),
+            _ => None
+        }
+    }
+
+    // Every hedgehog has to be saved: a single dead one loses the level,
+    // the level is won once the last alive hedgehog reached an exit.
+    pub fn get_outcome(&self) -> EnumPuzzleOutcome {
+        let mut is_any_alive = false;
+        for hedgehog in self.hedgehogs.iter() {
+            match hedgehog {
+                EnumHedgehogOnGrid::Empty => {},
+                EnumHedgehogOnGrid::HedgehogAlive => is_any_alive = true,
+                EnumHedgehogOnGrid::HedgehogDeadBurnt
+                | EnumHedgehogOnGrid::HedgehogDeadTooClose => return EnumPuzzleOutcome::Lost,
+            }
+        }
+        if !is_any_alive && self.hedgehog_saved_number > 0 {
+            return EnumPuzzleOutcome::Won
+        }
+        EnumPuzzleOutcome::Playing
+    }
+
+    // Resolves what happens to a hedgehog that just stepped on a tile.
+    fn resolve_hedgehog_on_tile(&mut self, position: GridPosition, report: &mut StepReport) {
+        match self.get_tile(&position) {
+            EnumeTileBehaviour::TileBFire => {
+                self.set_hedgehog(&position, EnumHedgehogOnGrid::HedgehogDeadBurnt);
+                report.events.push(
+                    EnumStepEvent::Died{
+                        position,
+                        cause: EnumHedgehogOnGrid::HedgehogDeadBurnt,
+                    }
+                );
+            },
+            EnumeTileBehaviour::TileBExit => {
+                self.set_hedgehog(&position, EnumHedgehogOnGrid::Empty);
+                self.hedgehog_saved_number += 1;
+                report.events.push(EnumStepEvent::Rescued{position});
+            },
+            _ => {}
+        }
+    }
+
+    // Kills every alive hedgehog standing next to another alive one.
+    // Neighbours are all found before killing anyone: both hedgehogs die.
+    fn resolve_hedgehogs_too_close(&mut self, report: &mut StepReport) {
+        let too_close_positions: Vec<GridPosition> = self.iter_positions()
+            .filter(|position| self.is_hedgehog_alive(position))
+            .filter(|position| DIRECTIONS.iter().any(|direction| {
+                match self.get_neighbour(position, *direction) {
+                    Some(neighbour) => self.is_hedgehog_alive(&neighbour),
+                    None => false,
+                }
+            }))
+            .collect();
+        for position in too_close_positions {
+            self.set_hedgehog(&position, EnumHedgehogOnGrid::HedgehogDeadTooClose);
+            report.events.push(
+                EnumStepEvent::Died{
+                    position,
+                    cause: EnumHedgehogOnGrid::HedgehogDeadTooClose,
+                }
+            );
+        }
+    }
+
+    // Moves every alive hedgehog one cell toward direction.
+    // Hedgehogs are resolved front first, so a line of hedgehogs moves as a
+    // whole while a hedgehog blocked by an obstacle blocks the ones behind it.
+    // Dead hedgehogs stay on their cell and block it.
+    // A hedgehog on water keeps sliding until it leaves the water or is
+    // blocked, only the tile it stops on is resolved.
+    // Once everyone moved, the level "too close" rule is applied.
+    pub fn step(&mut self, direction: EnumDirection) -> StepReport {
+        let mut report = StepReport::default();
+
+        let mut alive_positions: Vec<GridPosition> = self.iter_positions()
+            .filter(|position| self.is_hedgehog_alive(position))
+            .collect();
+        alive_positions.sort_by_key(|position| -direction.progress(position));
+
+        for from in alive_positions {
+            let Some(mut to) = self.get_next_free_position(&from, direction) else {
+                continue
+            };
+            while let EnumeTileBehaviour::TileBWater = self.get_tile(&to) {
+                let Some(next) = self.get_next_free_position(&to, direction) else {
+                    break
+                };
+                to = next;
+            }
+            let hedgehog = self.get_hedgehog(&from);
+            self.set_hedgehog(&from, EnumHedgehogOnGrid::Empty);
+            self.set_hedgehog(&to, hedgehog);
+            report.events.push(EnumStepEvent::Moved{from, to});
+            self.resolve_hedgehog_on_tile(to, &mut report);
+        }
+        if let EnumTooCloseRule::Die = self.too_close_rule {
+            self.resolve_hedgehogs_too_close(&mut report);
+        }
+        report
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::level::definition::hedgehog::EnumHedgehogOnGrid;
+use crate::level::definition::level_definition::LevelDescription;
+use crate::puzzle::puzzle_state::{EnumDirection, EnumPuzzleOutcome, PuzzleState, DIRECTIONS};
+
+// Breadth first search on PuzzleState: same rules as the game, first win
+// found is one of the shortest.
+
+// -- CONST / ENUM -----------------------------------------------------------
+
+pub const SOLVER_DEFAULT_MAX_MOVE_COUNT: usize = 100;
+pub const SOLVER_DEFAULT_MAX_STATE_COUNT: usize = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumSolverResult {
+    // Shortest winning moves, empty if state is already won.
+    Solved(Vec<EnumDirection>),
+    // Every reachable state was explored: level can not be won.
+    NoSolution,
+    // Search stopped on a bound before exploring every reachable state.
+    NoSolutionWithinBound,
+}
+
+// -- STRUCT -----------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverBounds {
+    pub max_move_count: usize,
+    pub max_state_count: usize,
+}
+
+// Explored state, with the step that reached it first.
+struct SolverNode {
+    state: PuzzleState,
+    parent: Option<(usize, EnumDirection)>,
+    move_count: usize,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl Default for SolverBounds {
+    fn default() -> Self {
+        SolverBounds {
+            max_move_count: SOLVER_DEFAULT_MAX_MOVE_COUNT,
+            max_state_count: SOLVER_DEFAULT_MAX_STATE_COUNT,
+        }
+    }
+}
+
+impl EnumSolverResult {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, EnumSolverResult::Solved(_))
+    }
+}
+
+pub fn solve_level_description(
+    level_description: &LevelDescription,
+    bounds: &SolverBounds,
+) -> EnumSolverResult {
+    solve(&PuzzleState::from_level_description(level_description), bounds)
+}
+
+// Tiles never change while playing: states are told apart by hedgehogs only.
+// Lost states and moves changing nothing are not explored further.
+pub fn solve(state: &PuzzleState, bounds: &SolverBounds) -> EnumSolverResult {
+    match state.get_outcome() {
+        EnumPuzzleOutcome::Won => return EnumSolverResult::Solved(Vec::new()),
+        EnumPuzzleOutcome::Lost => return EnumSolverResult::NoSolution,
+        EnumPuzzleOutcome::Playing => {},
+    }
+    let mut visited: HashSet<(Vec<EnumHedgehogOnGrid>, usize)> = HashSet::new();
+    visited.insert((state.hedgehogs.clone(), state.hedgehog_saved_number));
+    let mut nodes = vec![SolverNode{state: state.clone(), parent: None, move_count: 0}];
+    let mut queue = VecDeque::from([0]);
+    let mut is_bound_reached = false;
+
+    while let Some(node_idx) = queue.pop_front() {
+        if nodes[node_idx].move_count >= bounds.max_move_count {
+            is_bound_reached = true;
+            continue
+        }
+        for direction in DIRECTIONS {
+            let mut next_state = nodes[node_idx].state.clone();
+            if next_state.step(direction).is_empty() {
+                continue
+            }
+            if !visited.insert((next_state.hedgehogs.clone(), next_state.hedgehog_saved_number)) {
+                continue
+            }
+            match next_state.get_outcome() {
+                EnumPuzzleOutcome::Lost => continue,
+                EnumPuzzleOutcome::Won => {
+                    let mut moves = get_moves_to_node(&nodes, node_idx);
+                    moves.push(direction);
+                    return EnumSolverResult::Solved(moves)
+                },
+                EnumPuzzleOutcome::Playing => {},
+            }
+            if nodes.len() >= bounds.max_state_count {
+                return EnumSolverResult::NoSolutionWithinBound
+            }
+            let move_count = nodes[node_idx].move_count + 1;
+            nodes.push(SolverNode{state: next_state, parent: Some((node_idx, direction)), move_count});
+            queue.push_back(nodes.len() - 1);
+        }
+    }
+    if is_bound_reached {
+        EnumSolverResult::NoSolutionWithinBound
+    } else {
+        EnumSolverResult::NoSolution
+    }
+}
+
+fn get_moves_to_node(nodes: &[SolverNode], node_idx: usize) -> Vec<EnumDirection> {
+    let mut moves = Vec::new();
+    let mut current = node_idx;
+    while let Some((parent_idx, direction)) = nodes[current].parent {
+        moves.push(direction);
+        current = parent_idx;
+    }
+    moves.reverse();
+    moves
+}
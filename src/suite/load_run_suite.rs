@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+use crate::app::common::ResEpisodeLocation;
+use crate::episode::episode_definition::{get_episode_file_path, EpisodeDescription, ResCurEpisode};
+use crate::episode::load_run_episode::{s_run_only_episode, s_run_origin_level_on_curr_episode};
+use crate::episode::serialize_episode::{load_episode_description_from_file, spawn_episode_description};
+use crate::game::navigation::EventEpisodeExited;
+use crate::level::level::LevelSwitcher;
+use crate::suite::suite_definition::*;
+use crate::suite::serialize_suite::{load_suite_description_from_file, spawn_suite_description};
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginLoadRunSuite;
+
+impl Plugin for PluginLoadRunSuite {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                Update,
+                (
+                    s_run_next_episode_on_episode_exited,
+                    s_run_only_episode,
+                    s_run_origin_level_on_curr_episode,
+                ).chain().run_if(on_event::<EventEpisodeExited>())
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+pub fn s_load_only_suite(
+    mut commands: Commands,
+    r_type_registry: Res<AppTypeRegistry>,
+    r_suite_to_run: Res<ResSuiteToRun>,
+) {
+    let Some(suite_path) = &r_suite_to_run.suite_path else {
+        return
+    };
+    match load_suite_description_from_file(suite_path, &r_type_registry) {
+        Ok(suite_description) => {
+            spawn_suite_description(&mut commands, &suite_description);
+        },
+        Err(error) => error!("Suite not loaded: {error}"),
+    }
+}
+
+// Spawns the first episode to play: run it with s_run_only_episode.
+pub fn s_run_only_suite(
+    mut commands: Commands,
+    r_type_registry: Res<AppTypeRegistry>,
+    r_episode_location: Res<ResEpisodeLocation>,
+    q_suites: Query<(Entity, &SuiteUID, &SuiteEpisodes)>,
+    mut r_cur_suite: ResMut<ResCurSuite>,
+) {
+    let Ok((entity, suite_uid, suite_episodes)) = q_suites.get_single() else {
+        warn!("No single suite to run.");
+        return
+    };
+    run_suite(
+        &mut commands,
+        &r_type_registry,
+        &r_episode_location,
+        &mut r_cur_suite,
+        &entity,
+        suite_uid,
+        suite_episodes,
+    );
+}
+
+// Current episode is over: it is replaced by the next unlocked one, whose
+// origin level is then loaded.
+fn s_run_next_episode_on_episode_exited(
+    mut level_switcher: LevelSwitcher,
+    r_type_registry: Res<AppTypeRegistry>,
+    r_episode_location: Res<ResEpisodeLocation>,
+    r_cur_episode: Res<ResCurEpisode>,
+    q_suites: Query<&SuiteEpisodes>,
+    mut r_cur_suite: ResMut<ResCurSuite>,
+    mut e_episode_exited: EventReader<EventEpisodeExited>,
+) {
+    for event in e_episode_exited.read() {
+        r_cur_suite.completed_episode_uids.insert(event.episode_uid);
+    }
+    let Some(Ok(suite_episodes)) = r_cur_suite.suite_entity.map(|entity| q_suites.get(entity)) else {
+        return
+    };
+    let Some(episode_idx) = suite_episodes.get_next_episode_idx(&r_cur_suite.completed_episode_uids) else {
+        info!("Suite completed.");
+        r_cur_suite.current_episode_idx = None;
+        return
+    };
+
+    // current episode is left only once the next one is read.
+    let episode_description = match load_suite_episode(
+        &r_type_registry,
+        &r_episode_location,
+        suite_episodes,
+        episode_idx,
+    ) {
+        Ok(episode_description) => episode_description,
+        Err(error) => {
+            error!("Next episode not run: {error}");
+            return
+        }
+    };
+    level_switcher.switch_level();
+    let commands = &mut level_switcher.commands;
+    for level_entity in r_cur_episode.level_entities.values() {
+        commands.entity(*level_entity).despawn();
+    }
+    if let Some(episode_entity) = r_cur_episode.episode_entity {
+        commands.entity(episode_entity).despawn();
+    }
+    spawn_episode_description(commands, &episode_description);
+    r_cur_suite.current_episode_idx = Some(episode_idx);
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Completed episodes are kept: a suite run again resumes where it was left.
+pub fn run_suite(
+    commands: &mut Commands,
+    type_registry: &AppTypeRegistry,
+    r_episode_location: &ResEpisodeLocation,
+    r_cur_suite: &mut ResMut<ResCurSuite>,
+    suite_entity: &Entity,
+    suite_uid: &SuiteUID,
+    suite_episodes: &SuiteEpisodes,
+) {
+    r_cur_suite.suite_entity = Some(*suite_entity);
+    r_cur_suite.suite_uid = Some(suite_uid.uid);
+    r_cur_suite.current_episode_idx = None;
+
+    let Some(episode_idx) = suite_episodes.get_next_episode_idx(&r_cur_suite.completed_episode_uids) else {
+        info!("Suite {} already completed.", suite_uid.uid);
+        return
+    };
+    // spawned to be run with s_run_only_episode.
+    match load_suite_episode(type_registry, r_episode_location, suite_episodes, episode_idx) {
+        Ok(episode_description) => {
+            spawn_episode_description(commands, &episode_description);
+            r_cur_suite.current_episode_idx = Some(episode_idx);
+        },
+        Err(error) => error!("Episode not run: {error}"),
+    }
+}
+
+// Reads the episode file of a suite episode.
+fn load_suite_episode(
+    type_registry: &AppTypeRegistry,
+    r_episode_location: &ResEpisodeLocation,
+    suite_episodes: &SuiteEpisodes,
+    episode_idx: usize,
+) -> Result<EpisodeDescription, String> {
+    let suite_episode = &suite_episodes.episodes[episode_idx];
+    let file_path = get_episode_file_path(
+        &r_episode_location.episode_location,
+        &suite_episode.episode_filename
+    );
+    let episode_description = load_episode_description_from_file(&file_path, type_registry)
+        .map_err(|error| format!("episode {} of suite is missing: {error}", suite_episode.episode_uid))?;
+    if episode_description.episode_uid.uid != suite_episode.episode_uid {
+        warn!(
+            "Episode file {file_path} is episode {}, suite expects {}.",
+            episode_description.episode_uid.uid,
+            suite_episode.episode_uid
+        );
+    }
+    Ok(episode_description)
+}
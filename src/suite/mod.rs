@@ -0,0 +1,6 @@
+#[allow(clippy::module_inception)]
+pub mod suite;
+pub mod suite_definition;
+pub mod serialize_suite;
+pub mod load_run_suite;
+pub mod save_game;
@@ -0,0 +1,238 @@
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+use uuid::Uuid;
+
+use crate::config::StateGlobal;
+use crate::game::navigation::EventEpisodeExited;
+use crate::game::turn::EventLevelWon;
+use crate::level::actions::serialize::write_file;
+use crate::suite::suite_definition::{ResCurSuite, SuiteEpisodes};
+
+// -- CONST ------------------------------------------------------------------
+
+// Bumped on every format change, with its step in migrate_save_game.
+pub const SAVE_GAME_VERSION: u32 = 1;
+
+// From crate root: player progress is not an asset.
+pub const SAVE_GAME_DEFAULT_PATH: &str = "saves/save_game.ron";
+
+// -- COMPONENT / RESSOURCE --------------------------------------------------
+
+#[derive(Reflect, Default, Debug, Clone, PartialEq)]
+pub struct LevelProgress {
+    pub level_uid: Uuid,
+    pub best_move_count: usize,
+}
+
+// Player progress, keyed by level and episode uids.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct SaveGame {
+    pub version: u32,
+    pub completed_levels: Vec<LevelProgress>,
+    pub completed_episode_uids: Vec<Uuid>,
+    pub unlocked_episode_uids: Vec<Uuid>,
+}
+
+// file_path None: progress is kept for the session only.
+#[derive(Resource, Default)]
+pub struct ResSaveGame {
+    pub file_path: Option<String>,
+    pub save_game: SaveGame,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginSaveGame;
+
+impl Plugin for PluginSaveGame {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResSaveGame>()
+            .register_type::<LevelProgress>()
+            .register_type::<SaveGame>()
+            .add_systems(Startup, s_load_save_game)
+            .add_systems(
+                Update,
+                (
+                    s_save_game_on_level_won.run_if(
+                        in_state(StateGlobal::Game).and_then(on_event::<EventLevelWon>())
+                    ),
+                    s_save_game_on_episode_exited.run_if(on_event::<EventEpisodeExited>()),
+                )
+            );
+    }
+}
+
+// -- SYSTEM -----------------------------------------------------------------
+
+// Runs before suites do: completed episodes are skipped when a suite is run.
+fn s_load_save_game(
+    r_type_registry: Res<AppTypeRegistry>,
+    mut r_save_game: ResMut<ResSaveGame>,
+    mut r_cur_suite: ResMut<ResCurSuite>,
+) {
+    let Some(file_path) = r_save_game.file_path.clone() else {
+        return
+    };
+    if !std::path::Path::new(&file_path).exists() {
+        return
+    }
+    match load_save_game_from_file(&file_path, &r_type_registry) {
+        Ok(save_game) => {
+            r_cur_suite.completed_episode_uids.extend(save_game.completed_episode_uids.iter().copied());
+            r_save_game.save_game = save_game;
+        },
+        Err(error) => {
+            // file is left untouched rather than overwritten by a new game.
+            error!("Save game not loaded, progress will not be saved: {error}");
+            r_save_game.file_path = None;
+        }
+    }
+}
+
+fn s_save_game_on_level_won(
+    r_type_registry: Res<AppTypeRegistry>,
+    mut r_save_game: ResMut<ResSaveGame>,
+    mut e_level_won: EventReader<EventLevelWon>,
+) {
+    for event in e_level_won.read() {
+        if let Some(level_uid) = event.level_uid {
+            r_save_game.save_game.record_level_won(level_uid, event.move_count);
+        }
+    }
+    write_save_game(&r_save_game, &r_type_registry);
+}
+
+fn s_save_game_on_episode_exited(
+    r_type_registry: Res<AppTypeRegistry>,
+    r_cur_suite: Res<ResCurSuite>,
+    q_suites: Query<&SuiteEpisodes>,
+    mut r_save_game: ResMut<ResSaveGame>,
+    mut e_episode_exited: EventReader<EventEpisodeExited>,
+) {
+    for event in e_episode_exited.read() {
+        r_save_game.save_game.record_episode_completed(event.episode_uid);
+    }
+    if let Some(Ok(suite_episodes)) = r_cur_suite.suite_entity.map(|entity| q_suites.get(entity)) {
+        r_save_game.save_game.record_unlocked_episodes(suite_episodes);
+    }
+    write_save_game(&r_save_game, &r_type_registry);
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+impl Default for SaveGame {
+    fn default() -> Self {
+        SaveGame {
+            version: SAVE_GAME_VERSION,
+            completed_levels: Vec::new(),
+            completed_episode_uids: Vec::new(),
+            unlocked_episode_uids: Vec::new(),
+        }
+    }
+}
+
+impl SaveGame {
+    pub fn is_level_completed(&self, level_uid: Uuid) -> bool {
+        self.get_best_move_count(level_uid).is_some()
+    }
+
+    pub fn get_best_move_count(&self, level_uid: Uuid) -> Option<usize> {
+        self.completed_levels.iter()
+            .find(|level_progress| level_progress.level_uid == level_uid)
+            .map(|level_progress| level_progress.best_move_count)
+    }
+
+    // Keeps the lowest move count the level was ever won with.
+    pub fn record_level_won(&mut self, level_uid: Uuid, move_count: usize) {
+        match self.completed_levels.iter_mut().find(|level_progress| level_progress.level_uid == level_uid) {
+            Some(level_progress) => {
+                level_progress.best_move_count = level_progress.best_move_count.min(move_count);
+            },
+            None => self.completed_levels.push(LevelProgress{level_uid, best_move_count: move_count}),
+        }
+    }
+
+    pub fn record_episode_completed(&mut self, episode_uid: Uuid) {
+        if !self.completed_episode_uids.contains(&episode_uid) {
+            self.completed_episode_uids.push(episode_uid);
+        }
+    }
+
+    // Episodes stay unlocked once they have been, even if the suite changes.
+    pub fn record_unlocked_episodes(&mut self, suite_episodes: &SuiteEpisodes) {
+        let completed_episode_uids = self.completed_episode_uids.iter().copied().collect();
+        for (idx, suite_episode) in suite_episodes.episodes.iter().enumerate() {
+            if self.unlocked_episode_uids.contains(&suite_episode.episode_uid) {
+                continue
+            }
+            if suite_episodes.is_episode_unlocked(idx, &completed_episode_uids) {
+                self.unlocked_episode_uids.push(suite_episode.episode_uid);
+            }
+        }
+    }
+}
+
+// Brings a save game of any older version up to SAVE_GAME_VERSION.
+pub fn migrate_save_game(save_game: SaveGame) -> Result<SaveGame, String> {
+    match save_game.version {
+        SAVE_GAME_VERSION => Ok(save_game),
+        version if version > SAVE_GAME_VERSION => Err(
+            format!("save game version {version} is newer than supported {SAVE_GAME_VERSION}")
+        ),
+        version => Err(format!("no migration from save game version {version}")),
+    }
+}
+
+// Serializes save game as a one entity scene.
+pub fn serialize_save_game(
+    save_game: &SaveGame,
+    type_registry: &AppTypeRegistry,
+) -> Result<String, String> {
+    let mut scene_world = World::new();
+    scene_world.insert_resource(type_registry.clone());
+    scene_world.spawn(save_game.clone());
+    let scene = DynamicScene::from_world(&scene_world);
+    scene.serialize(&type_registry.read()).map_err(|error| error.to_string())
+}
+
+// Reads back a file written by serialize_save_game, migrated to the current
+// version.
+pub fn deserialize_save_game(
+    serialized: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<SaveGame, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(serialized, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+    let save_game = scene.entities.iter()
+        .flat_map(|entity| entity.components.iter())
+        .find_map(|component| SaveGame::from_reflect(component.as_ref()))
+        .ok_or("no save game found".to_string())?;
+    migrate_save_game(save_game)
+}
+
+// file_path is from crate root.
+pub fn load_save_game_from_file(
+    file_path: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<SaveGame, String> {
+    let serialized = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    deserialize_save_game(&serialized, type_registry)
+        .map_err(|error| format!("{file_path}: {error}"))
+}
+
+fn write_save_game(r_save_game: &ResSaveGame, type_registry: &AppTypeRegistry) {
+    let Some(file_path) = &r_save_game.file_path else {
+        return
+    };
+    let result = serialize_save_game(&r_save_game.save_game, type_registry)
+        .and_then(|serialized| write_file(file_path, &serialized));
+    if let Err(error) = result {
+        error!("Save game not written: {error}");
+    }
+}
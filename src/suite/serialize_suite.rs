@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+use bevy::scene::ron;
+use bevy::scene::serde::SceneDeserializer;
+
+use crate::suite::suite_definition::{
+    SuiteDescription,
+    SuiteEpisodes,
+    SuiteInfo,
+    SuiteUID,
+};
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Serializes suite as a one entity scene.
+pub fn serialize_suite_description(
+    suite_description: &SuiteDescription,
+    type_registry: &AppTypeRegistry,
+) -> Result<String, String> {
+    let mut scene_world = World::new();
+    scene_world.insert_resource(type_registry.clone());
+    scene_world.spawn(
+        (
+            suite_description.suite_info.clone(),
+            suite_description.suite_uid.clone(),
+            suite_description.suite_episodes.clone(),
+        )
+    );
+    let scene = DynamicScene::from_world(&scene_world);
+    scene.serialize(&type_registry.read()).map_err(|error| error.to_string())
+}
+
+// Reads back a file written by serialize_suite_description.
+pub fn deserialize_suite_description(
+    serialized: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<SuiteDescription, String> {
+    let type_registry = type_registry.read();
+    let scene = ron::Options::default()
+        .from_str_seed(serialized, SceneDeserializer{type_registry: &type_registry})
+        .map_err(|error| error.to_string())?;
+
+    let mut suite_info: Option<SuiteInfo> = None;
+    let mut suite_uid: Option<SuiteUID> = None;
+    let mut suite_episodes: Option<SuiteEpisodes> = None;
+    for component in scene.entities.iter().flat_map(|entity| entity.components.iter()) {
+        let component = component.as_ref();
+        if let Some(value) = SuiteInfo::from_reflect(component) {
+            suite_info = Some(value);
+        } else if let Some(value) = SuiteUID::from_reflect(component) {
+            suite_uid = Some(value);
+        } else if let Some(value) = SuiteEpisodes::from_reflect(component) {
+            suite_episodes = Some(value);
+        }
+    }
+    let (Some(suite_info), Some(suite_uid), Some(suite_episodes)) =
+        (suite_info, suite_uid, suite_episodes) else {
+        return Err("no suite found".to_string())
+    };
+    Ok(
+        SuiteDescription {
+            suite_info,
+            suite_uid,
+            suite_episodes,
+        }
+    )
+}
+
+// file_path is from crate root, as SuiteInfo.suite_path.
+pub fn load_suite_description_from_file(
+    file_path: &str,
+    type_registry: &AppTypeRegistry,
+) -> Result<SuiteDescription, String> {
+    let serialized = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("{file_path}: {error}"))?;
+    deserialize_suite_description(&serialized, type_registry)
+        .map_err(|error| format!("{file_path}: {error}"))
+}
+
+pub fn spawn_suite_description(
+    commands: &mut Commands,
+    suite_description: &SuiteDescription,
+) -> Entity {
+    commands.spawn(
+        (
+            suite_description.suite_info.clone(),
+            suite_description.suite_uid.clone(),
+            suite_description.suite_episodes.clone(),
+        )
+    ).id()
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+use crate::suite::suite_definition::PluginSuiteDefinition;
+use crate::suite::load_run_suite::PluginLoadRunSuite;
+use crate::suite::save_game::PluginSaveGame;
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginSuite;
+
+impl Plugin for PluginSuite {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(PluginSuiteDefinition)
+            .add_plugins(PluginLoadRunSuite)
+            .add_plugins(PluginSaveGame);
+    }
+}
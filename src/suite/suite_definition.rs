@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use uuid::Uuid;
+
+// -- CONSTS -----------------------------------------------------------------
+
+pub const SUITE_LOCATION: &str = "suites";
+
+pub const SUITE_DEFAULT_FILENAME: &str = "main";
+
+// -- ENUM -------------------------------------------------------------------
+
+// When an episode of a suite can be played.
+#[derive(Reflect, Default, Debug, Clone, PartialEq, Eq)]
+pub enum EnumEpisodeUnlockRule {
+    #[default]
+    Unlocked,
+    AfterPreviousEpisode, // previous episode of the suite completed.
+    AfterEpisodes(Vec<Uuid>), // every listed episode completed.
+}
+
+// -- COMPONENTS -------------------------------------------------------------
+
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
+pub struct SuiteInfo{
+    pub suite_name: String,
+    pub suite_path: String, // suite file, from crate root.
+}
+
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
+pub struct SuiteUID{
+    pub uid: Uuid,
+}
+
+#[derive(Reflect, Default, Debug, Clone)]
+pub struct SuiteEpisode{
+    pub episode_uid: Uuid,
+    pub episode_filename: String, // in the episode location of the app.
+    pub unlock_rule: EnumEpisodeUnlockRule,
+}
+
+// Episodes in play order.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component)]
+pub struct SuiteEpisodes{
+    pub episodes: Vec<SuiteEpisode>,
+}
+
+// Whole suite as written on disk: components of the suite entity. Episodes
+// keep their own files.
+#[derive(Default, Clone)]
+pub struct SuiteDescription{
+    pub suite_info: SuiteInfo,
+    pub suite_uid: SuiteUID,
+    pub suite_episodes: SuiteEpisodes,
+}
+
+// -- FUNCS ------------------------------------------------------------------
+
+// Path of a suite file, from crate root.
+pub fn get_suite_file_path(suite_filename: &str) -> String {
+    format!("assets/{SUITE_LOCATION}/{suite_filename}.ron")
+}
+
+impl SuiteEpisodes {
+    pub fn is_episode_unlocked(&self, episode_idx: usize, completed_episode_uids: &HashSet<Uuid>) -> bool {
+        let Some(suite_episode) = self.episodes.get(episode_idx) else {
+            return false
+        };
+        match &suite_episode.unlock_rule {
+            EnumEpisodeUnlockRule::Unlocked => true,
+            EnumEpisodeUnlockRule::AfterPreviousEpisode => {
+                episode_idx.checked_sub(1)
+                    .and_then(|previous_idx| self.episodes.get(previous_idx))
+                    .is_none_or(|previous| completed_episode_uids.contains(&previous.episode_uid))
+            },
+            EnumEpisodeUnlockRule::AfterEpisodes(episode_uids) => {
+                episode_uids.iter().all(|uid| completed_episode_uids.contains(uid))
+            },
+        }
+    }
+
+    // First unlocked episode not completed yet, None once the suite is over.
+    pub fn get_next_episode_idx(&self, completed_episode_uids: &HashSet<Uuid>) -> Option<usize> {
+        (0..self.episodes.len()).find(|idx| {
+            !completed_episode_uids.contains(&self.episodes[*idx].episode_uid)
+                && self.is_episode_unlocked(*idx, completed_episode_uids)
+        })
+    }
+}
+
+// -- RESSOURCES -------------------------------------------------------------
+
+// Suite file to run on startup, None to run no suite.
+#[derive(Resource, Default)]
+pub struct ResSuiteToRun{
+    pub suite_path: Option<String>,
+}
+
+#[derive(Resource, Default)]
+pub struct ResCurSuite{
+    pub suite_entity: Option<Entity>,
+    pub suite_uid: Option<Uuid>,
+    pub current_episode_idx: Option<usize>,
+    pub completed_episode_uids: HashSet<Uuid>,
+}
+
+// -- PLUGIN -----------------------------------------------------------------
+
+pub struct PluginSuiteDefinition;
+
+impl Plugin for PluginSuiteDefinition {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ResSuiteToRun>()
+            .init_resource::<ResCurSuite>()
+            .register_type::<EnumEpisodeUnlockRule>()
+            .register_type::<SuiteEpisode>()
+            .register_type::<SuiteInfo>()
+            .register_type::<SuiteUID>()
+            .register_type::<SuiteEpisodes>();
+    }
+}
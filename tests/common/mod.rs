@@ -0,0 +1,140 @@
+// Helpers shared by integration tests, pulled by each test file with
+// `mod common;`.
+//
+// Grids are written one row per x, one char per z:
+//   '.' floor, '#' obstacle, 'F' fire, 'W' water, 'E' exit, ' ' empty,
+//   'h' hedgehog on floor, 'w' hedgehog on water.
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use hedgeclog::asset::asset_loader::HedgehogAssets;
+use hedgeclog::level::actions::edit_level::{
+    EventHedgehogCreationAsked,
+    EventTileCreationAsked,
+    PluginEditLevel,
+};
+use hedgeclog::level::actions::edit_level_history::PluginEditLevelHistory;
+use hedgeclog::level::definition::hedgehog::{EnumHedgehogOnGrid, HedgehogType, ResHedgeHogInfo};
+use hedgeclog::level::definition::level_definition::{
+    GridPosition,
+    LevelGridHedgehog,
+    LevelGridTile,
+    PluginLevelDefinition,
+    ResCurrentLevelGrid,
+};
+use hedgeclog::level::definition::tiles::{
+    get_tile_transform_on_grid,
+    DefinitionTile,
+    EnumTileOrientation,
+    EnumTilesId,
+    EnumeTileBehaviour,
+    ResCollectionTile,
+};
+use hedgeclog::puzzle::puzzle_state::PuzzleState;
+
+// -- LEVEL EDITION ----------------------------------------------------------
+
+pub const LEVEL_SIZE: usize = 4;
+
+// Indexes in the stub tile collection.
+pub const TILE_IDX_FLOOR: usize = 0;
+pub const TILE_IDX_FIRE: usize = 1;
+pub const TILE_IDX_WALL: usize = 2;
+
+// Tiles have no model and hedgehogs no sprite: nothing is rendered anyway.
+pub fn build_level_edit_app() -> App {
+    let mut app = App::new();
+    app
+        .add_plugins((MinimalPlugins, PluginLevelDefinition, PluginEditLevel, PluginEditLevelHistory))
+        .init_resource::<HedgehogAssets>()
+        .init_resource::<ResHedgeHogInfo>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .insert_resource(ResCollectionTile{
+            tiles: [EnumTilesId::TileIdFloor, EnumTilesId::TileIdFire, EnumTilesId::TileIdWall]
+                .into_iter()
+                .map(|tile_id| DefinitionTile{
+                    tile_id,
+                    tile_model: Handle::default(),
+                    tile_behaviour: tile_id.get_behaviour(),
+                })
+                .collect(),
+        })
+        .insert_resource(ResCurrentLevelGrid::new(LEVEL_SIZE, LEVEL_SIZE));
+    app
+}
+
+// One edit per frame, as the editor asks them: validation of the edit and
+// its commands are done two frames later at most.
+pub fn ask_edit<E: Event>(app: &mut App, event: E) {
+    app.world_mut().send_event(event);
+    app.update();
+    app.update();
+}
+
+pub fn get_tile_creation(tile_idx: usize, grid_position: GridPosition) -> EventTileCreationAsked {
+    EventTileCreationAsked{
+        tile_idx,
+        tile_transform: get_tile_transform_on_grid(&grid_position, EnumTileOrientation::Deg0),
+        grid_position,
+    }
+}
+
+pub fn ask_tile_creation(app: &mut App, tile_idx: usize, grid_position: GridPosition) {
+    ask_edit(app, get_tile_creation(tile_idx, grid_position));
+}
+
+pub fn ask_hedgehog_creation(app: &mut App, grid_position: GridPosition) {
+    ask_edit(app, EventHedgehogCreationAsked{
+        hedgehog_transform: Transform::default(),
+        grid_position,
+    });
+}
+
+pub fn get_tile_id(app: &App, grid_position: GridPosition) -> Option<EnumTilesId> {
+    app.world().resource::<ResCurrentLevelGrid>().level_grid[grid_position.x][grid_position.z].tile_id
+}
+
+pub fn has_hedgehog(app: &App, grid_position: GridPosition) -> bool {
+    app.world().resource::<ResCurrentLevelGrid>().hedgehog_grid[grid_position.x][grid_position.z]
+        .hedgehog_entity
+        .is_some()
+}
+
+// -- PUZZLE -----------------------------------------------------------------
+
+// Logic grid only: no tile id, no entity.
+pub fn build_grid(rows: &[&str]) -> ResCurrentLevelGrid {
+    let depth = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut grid = ResCurrentLevelGrid::new(rows.len(), depth);
+    for (x, row) in rows.iter().enumerate() {
+        for (z, c) in row.chars().enumerate() {
+            let tile_behaviour = match c {
+                '.' | 'h' => EnumeTileBehaviour::TileBFloor,
+                'W' | 'w' => EnumeTileBehaviour::TileBWater,
+                '#' => EnumeTileBehaviour::TileBObstacle,
+                'F' => EnumeTileBehaviour::TileBFire,
+                'E' => EnumeTileBehaviour::TileBExit,
+                _ => EnumeTileBehaviour::Empty,
+            };
+            grid.level_grid[x][z] = LevelGridTile {
+                tile_behaviour,
+                ..Default::default()
+            };
+            if c == 'h' || c == 'w' {
+                grid.hedgehog_grid[x][z] = LevelGridHedgehog {
+                    hedgehog_behaviour: EnumHedgehogOnGrid::HedgehogAlive,
+                    hedgehog_entity: None,
+                    hedgehog_tile: Some(HedgehogType::HedegehogeTypeStandard),
+                };
+            }
+        }
+    }
+    grid
+}
+
+pub fn build_state(rows: &[&str]) -> PuzzleState {
+    PuzzleState::from_level_grid(&build_grid(rows))
+}
@@ -0,0 +1,67 @@
+// Editor help is generated from the bindings registered by each mode, and
+// bindings tell "r" from "ctrl+r".
+
+use bevy::prelude::*;
+
+use hedgeclog::editor::common::StateEditorMode;
+use hedgeclog::editor::keymap::{EditorBinding, EnumEditorInput, ResEditorKeymap, EDITOR_MODES_EDITION};
+
+const BINDING_MODE_HEDGEHOG: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyY]),
+    description: "mode \"hedgehog\"",
+};
+const BINDING_ADD_HEDGEHOG: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Mouse(MouseButton::Left),
+    description: "add hedgehog",
+};
+const BINDING_ROTATE_TILE: EditorBinding = EditorBinding{
+    input: EnumEditorInput::Keys(&[KeyCode::KeyR]),
+    description: "rotate tile",
+};
+const BINDING_REDO: EditorBinding = EditorBinding{
+    input: EnumEditorInput::CtrlKey(KeyCode::KeyR),
+    description: "redo edit",
+};
+
+fn get_keymap() -> ResEditorKeymap {
+    let mut keymap = ResEditorKeymap::default();
+    keymap.register_bindings(EDITOR_MODES_EDITION, "mode transition", &[BINDING_MODE_HEDGEHOG]);
+    keymap.register_bindings(&[StateEditorMode::Tile], "tile mode", &[BINDING_ROTATE_TILE]);
+    keymap.register_bindings(&[StateEditorMode::Hedgehog], "hedgehog mode", &[BINDING_ADD_HEDGEHOG]);
+    keymap.register_bindings(EDITOR_MODES_EDITION, "mode transition", &[BINDING_REDO]);
+    keymap
+}
+
+#[test]
+fn help_shows_sections_active_in_current_mode_only() {
+    let help_text = get_keymap().get_help_text(StateEditorMode::Hedgehog);
+    assert!(help_text.contains("y:"));
+    assert!(help_text.contains("mode \"hedgehog\""));
+    assert!(help_text.contains("Left click:"));
+    assert!(help_text.contains("add hedgehog"));
+    assert!(!help_text.contains("rotate tile"));
+
+    // try mode plays the level: edition bindings are not active there.
+    let help_text = get_keymap().get_help_text(StateEditorMode::Try);
+    assert!(!help_text.contains("mode \"hedgehog\""));
+}
+
+#[test]
+fn bindings_of_same_section_are_merged() {
+    let keymap = get_keymap();
+    assert_eq!(keymap.sections.len(), 3);
+    assert_eq!(keymap.sections[0].bindings, vec![BINDING_MODE_HEDGEHOG, BINDING_REDO]);
+    assert_eq!(BINDING_REDO.get_label(), "ctrl+r");
+}
+
+#[test]
+fn ctrl_tells_key_from_ctrl_key() {
+    let mut keyboard_input = ButtonInput::<KeyCode>::default();
+    keyboard_input.press(KeyCode::KeyR);
+    assert!(BINDING_ROTATE_TILE.is_key_just_pressed(&keyboard_input));
+    assert!(!BINDING_REDO.is_key_just_pressed(&keyboard_input));
+
+    keyboard_input.press(KeyCode::ControlLeft);
+    assert!(!BINDING_ROTATE_TILE.is_key_just_pressed(&keyboard_input));
+    assert!(BINDING_REDO.is_key_just_pressed(&keyboard_input));
+}
@@ -0,0 +1,63 @@
+// Running an episode resolves every level of its grid.
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use hedgeclog::episode::episode_definition::{
+    EpisodeGrid,
+    EpisodeInfo,
+    EpisodeUID,
+    PluginEpsiodeDefinition,
+    ResCurEpisode,
+};
+use hedgeclog::episode::load_run_episode::s_run_only_episode;
+use hedgeclog::level::actions::load_run_level::spawn_level_description;
+use hedgeclog::level::definition::level_definition::{LevelDescription, LevelUid, PluginLevelDefinition};
+use uuid::Uuid;
+
+#[test]
+fn run_episode_registers_spawned_levels_and_reports_missing_ones() {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition));
+    let world = app.world_mut();
+
+    let spawned_level = LevelDescription::new(Uuid::new_v4(), 3, 3);
+    let spawned_level_entity = spawn_level_description(&mut world.commands(), &spawned_level);
+    let missing_level_uid = Uuid::new_v4();
+
+    let mut episode_grid = EpisodeGrid::default();
+    episode_grid.episode_grid[0][5] = Some(spawned_level.uuid);
+    episode_grid.episode_grid[1][5] = Some(missing_level_uid);
+    world.spawn((EpisodeInfo::default(), EpisodeUID{uid: Uuid::new_v4()}, episode_grid));
+    world.flush();
+
+    world.run_system_once(s_run_only_episode);
+
+    let r_cur_episode = world.resource::<ResCurEpisode>();
+    assert!(r_cur_episode.is_level_ready(&spawned_level.uuid));
+    assert_eq!(r_cur_episode.level_entities.get(&spawned_level.uuid), Some(&spawned_level_entity));
+    assert!(!r_cur_episode.is_level_ready(&missing_level_uid));
+    assert_eq!(r_cur_episode.missing_level_uids, vec![missing_level_uid]);
+}
+
+#[test]
+fn run_episode_registers_level_entity_rather_than_its_tiles() {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition));
+    let world = app.world_mut();
+
+    // tiles and hedgehogs of a run level are tagged with its uid.
+    let level = LevelDescription::new(Uuid::new_v4(), 3, 3);
+    world.spawn(LevelUid{uid: Some(level.uuid)});
+    let level_entity = spawn_level_description(&mut world.commands(), &level);
+    world.spawn(LevelUid{uid: Some(level.uuid)});
+
+    let mut episode_grid = EpisodeGrid::default();
+    episode_grid.episode_grid[0][5] = Some(level.uuid);
+    world.spawn((EpisodeInfo::default(), EpisodeUID{uid: Uuid::new_v4()}, episode_grid));
+    world.flush();
+
+    world.run_system_once(s_run_only_episode);
+
+    let r_cur_episode = world.resource::<ResCurEpisode>();
+    assert_eq!(r_cur_episode.level_entities.get(&level.uuid), Some(&level_entity));
+}
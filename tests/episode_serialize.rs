@@ -0,0 +1,103 @@
+// Whole episode <-> RON, and episode spawned back from its description.
+
+use bevy::prelude::*;
+use hedgeclog::episode::episode_definition::{
+    EpisodeDescription,
+    EpisodeGrid,
+    EpisodeInfo,
+    EpisodeUID,
+    PluginEpsiodeDefinition,
+    get_episode_file_path,
+};
+use hedgeclog::episode::serialize_episode::{
+    deserialize_episode_description,
+    serialize_episode_description,
+    spawn_episode_description,
+};
+use hedgeclog::level::actions::serialize::serialize_level_description;
+use hedgeclog::level::definition::hedgehog::{EnumHedgehogOnGrid, HedgehogType};
+use hedgeclog::level::definition::level_definition::{
+    LevelDescription,
+    LevelGrid,
+    LevelUid,
+    PluginLevelDefinition,
+};
+use hedgeclog::level::definition::tiles::{EnumTilesId, EnumeTileBehaviour};
+use uuid::Uuid;
+
+fn build_type_registry() -> AppTypeRegistry {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition));
+    app.world().resource::<AppTypeRegistry>().clone()
+}
+
+// Two levels side by side, first one with a hedgehog on a floor.
+fn build_episode_description() -> EpisodeDescription {
+    let mut first_level = LevelDescription::new(Uuid::new_v4(), 3, 2);
+    first_level.level_grid[1][1].tile = Some(EnumTilesId::TileIdFloor);
+    first_level.level_grid[1][1].hedgehog = Some(HedgehogType::HedegehogeTypeStandard);
+    let mut second_level = LevelDescription::new(Uuid::new_v4(), 5, 5);
+    second_level.level_grid[4][4].tile = Some(EnumTilesId::TileIdExit);
+
+    let mut episode_grid = EpisodeGrid::default();
+    episode_grid.episode_grid[0][5] = Some(first_level.uuid);
+    episode_grid.episode_grid[0][6] = Some(second_level.uuid);
+
+    EpisodeDescription {
+        episode_info: EpisodeInfo {
+            level_name: "test".to_string(),
+            level_path: get_episode_file_path("episodes_testbed", "test"),
+        },
+        episode_uid: EpisodeUID { uid: Uuid::new_v4() },
+        episode_grid,
+        levels: vec![first_level, second_level],
+    }
+}
+
+#[test]
+fn episode_description_round_trip() {
+    let type_registry = build_type_registry();
+    let episode_description = build_episode_description();
+
+    let serialized = serialize_episode_description(&episode_description, &type_registry).unwrap();
+    let deserialized = deserialize_episode_description(&serialized, &type_registry).unwrap();
+
+    assert_eq!(deserialized.episode_uid.uid, episode_description.episode_uid.uid);
+    assert_eq!(deserialized.episode_info.level_path, "assets/episodes_testbed/test.ron");
+    assert_eq!(deserialized.episode_grid.episode_grid, episode_description.episode_grid.episode_grid);
+    assert_eq!(deserialized.levels.len(), 2);
+    for level in episode_description.levels.iter() {
+        let found = deserialized.levels.iter().find(|i_level| i_level.uuid == level.uuid).unwrap();
+        assert_eq!((found.width, found.depth), (level.width, level.depth));
+    }
+}
+
+#[test]
+fn level_file_is_not_an_episode() {
+    let type_registry = build_type_registry();
+    let level_description = LevelDescription::new(Uuid::new_v4(), 2, 2);
+    let serialized = serialize_level_description(&level_description, &type_registry).unwrap();
+
+    assert!(deserialize_episode_description(&serialized, &type_registry).is_err());
+}
+
+#[test]
+fn spawned_episode_has_every_level_entity() {
+    let episode_description = build_episode_description();
+    let mut world = World::new();
+    let episode_entity = spawn_episode_description(&mut world.commands(), &episode_description);
+    world.flush();
+
+    let episode_uid = world.get::<EpisodeUID>(episode_entity).unwrap();
+    assert_eq!(episode_uid.uid, episode_description.episode_uid.uid);
+
+    let first_level_uid = episode_description.levels[0].uuid;
+    let mut q_levels = world.query::<(&LevelUid, &LevelGrid)>();
+    assert_eq!(q_levels.iter(&world).count(), 2);
+    let (_, level_grid) = q_levels.iter(&world)
+        .find(|(level_uid, _)| level_uid.uid == Some(first_level_uid))
+        .unwrap();
+    assert_eq!(level_grid.level_grid[1][1].tile_behaviour, EnumeTileBehaviour::TileBFloor);
+    assert_eq!(level_grid.level_grid[0][0].tile_behaviour, EnumeTileBehaviour::Empty);
+    assert_eq!(level_grid.hedgehog_grid[1][1].hedgehog_behaviour, EnumHedgehogOnGrid::HedgehogAlive);
+}
@@ -0,0 +1,58 @@
+// Levels as ascii grids, and what makes a level or an episode unplayable.
+
+use hedgeclog::episode::episode_definition::{
+    EpisodeDescription,
+    EpisodeGrid,
+    DEFAULT_EPISODE_START_LOCATION,
+};
+use hedgeclog::level::actions::ascii_level::{level_description_from_ascii, level_description_to_ascii};
+use hedgeclog::level::definition::hedgehog::EnumTooCloseRule;
+use hedgeclog::level::definition::tiles::EnumTilesId;
+
+const ASCII_LEVEL: &str = "uuid=6e3a949c-cbbb-475f-a020-676e22394b91
+size=3x5
+too_close_rule=Die
+h.F
+#WE
+";
+
+#[test]
+fn ascii_level_round_trip() {
+    let level_description = level_description_from_ascii(ASCII_LEVEL).unwrap();
+    assert_eq!((level_description.width, level_description.depth), (3, 5));
+    assert_eq!(level_description.too_close_rule, EnumTooCloseRule::Die);
+    assert_eq!(level_description.level_grid[0][0].tile, Some(EnumTilesId::TileIdFloor));
+    assert!(level_description.level_grid[0][0].hedgehog.is_some());
+    assert_eq!(level_description.level_grid[1][2].tile, Some(EnumTilesId::TileIdExit));
+    assert_eq!(level_description.level_grid[2][4].tile, None);
+
+    let ascii = level_description_to_ascii(&level_description);
+    let read_back = level_description_from_ascii(&ascii).unwrap();
+    assert_eq!(read_back.uuid, level_description.uuid);
+    assert_eq!(level_description_to_ascii(&read_back), ascii);
+}
+
+#[test]
+fn unknown_tile_is_an_error() {
+    assert!(level_description_from_ascii("h?E").is_err());
+    assert!(level_description_from_ascii("size=1x2\nh.E").is_err());
+}
+
+#[test]
+fn unplayable_levels_and_episodes_are_reported() {
+    let level_description = level_description_from_ascii(ASCII_LEVEL).unwrap();
+    assert!(level_description.get_errors().is_empty());
+    let no_exit = level_description_from_ascii("hW.").unwrap();
+    assert_eq!(no_exit.get_errors(), vec!["level has no exit".to_string()]);
+
+    let mut episode_description = EpisodeDescription {
+        episode_grid: EpisodeGrid::default(),
+        levels: vec![level_description.clone()],
+        ..Default::default()
+    };
+    assert_eq!(episode_description.get_errors().len(), 2); // not on grid, no start level.
+
+    let [x, y] = DEFAULT_EPISODE_START_LOCATION;
+    episode_description.episode_grid.episode_grid[x][y] = Some(level_description.uuid);
+    assert!(episode_description.get_errors().is_empty());
+}
@@ -0,0 +1,157 @@
+// Level edition through edit_level events, on a headless app with stub
+// assets: after every edit, the grid and the spawned entities agree, and
+// hedgehogs only stand on floor.
+
+mod common;
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use common::{
+    ask_edit,
+    ask_hedgehog_creation,
+    ask_tile_creation,
+    build_level_edit_app,
+    get_tile_id,
+    has_hedgehog,
+    LEVEL_SIZE,
+    TILE_IDX_FIRE,
+    TILE_IDX_FLOOR,
+    TILE_IDX_WALL,
+};
+use hedgeclog::level::actions::edit_level::{EventHedgehogRemovalAsked, EventTileRemovalAsked, LevelSpawner, restore_level_grid};
+use hedgeclog::level::definition::hedgehog::{EnumHedgehogOnGrid, MarkerHedgehogOnLevel};
+use hedgeclog::level::definition::level_definition::{GridPosition, ResCurrentLevelGrid};
+use hedgeclog::level::definition::tiles::{EnumTilesId, MarkerTileOnLevel};
+
+const POSITION: GridPosition = GridPosition{x: 1, z: 2};
+const OTHER_POSITION: GridPosition = GridPosition{x: 3, z: 0};
+
+
+// Every entity of the grid is spawned at its grid position, and no other
+// tile / hedgehog entity is left.
+fn assert_grid_matches_entities(app: &mut App) {
+    let world = app.world_mut();
+    let r_grid = world.resource::<ResCurrentLevelGrid>().clone();
+    let mut q_tiles = world.query_filtered::<(Entity, &GridPosition), With<MarkerTileOnLevel>>();
+    let tile_entities: Vec<(Entity, GridPosition)> = q_tiles.iter(world)
+        .map(|(entity, grid_position)| (entity, *grid_position))
+        .collect();
+    let mut q_hedgehogs = world.query_filtered::<(Entity, &GridPosition), With<MarkerHedgehogOnLevel>>();
+    let hedgehog_entities: Vec<(Entity, GridPosition)> = q_hedgehogs.iter(world)
+        .map(|(entity, grid_position)| (entity, *grid_position))
+        .collect();
+
+    let mut grid_tile_entities = Vec::new();
+    let mut grid_hedgehog_entities = Vec::new();
+    for x in 0..LEVEL_SIZE {
+        for z in 0..LEVEL_SIZE {
+            let grid_position = GridPosition{x, z};
+            let tile = &r_grid.level_grid[x][z];
+            assert_eq!(tile.tile_id.is_some(), tile.tile_entity.is_some(), "tile at {grid_position:?}");
+            if let Some(entity) = tile.tile_entity {
+                grid_tile_entities.push((entity, grid_position));
+            }
+            let hedgehog = &r_grid.hedgehog_grid[x][z];
+            assert_eq!(
+                hedgehog.hedgehog_behaviour != EnumHedgehogOnGrid::Empty,
+                hedgehog.hedgehog_entity.is_some(),
+                "hedgehog at {grid_position:?}",
+            );
+            if let Some(entity) = hedgehog.hedgehog_entity {
+                grid_hedgehog_entities.push((entity, grid_position));
+            }
+        }
+    }
+    for entities in [&mut grid_tile_entities, &mut grid_hedgehog_entities] {
+        entities.sort_by_key(|(entity, _)| *entity);
+    }
+    let mut tile_entities = tile_entities;
+    let mut hedgehog_entities = hedgehog_entities;
+    for entities in [&mut tile_entities, &mut hedgehog_entities] {
+        entities.sort_by_key(|(entity, _)| *entity);
+    }
+    assert_eq!(tile_entities, grid_tile_entities);
+    assert_eq!(hedgehog_entities, grid_hedgehog_entities);
+}
+
+#[test]
+fn tile_is_replaced_and_removed() {
+    let mut app = build_level_edit_app();
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_tile_creation(&mut app, TILE_IDX_WALL, POSITION);
+    assert_eq!(get_tile_id(&app, POSITION), Some(EnumTilesId::TileIdWall));
+    assert_grid_matches_entities(&mut app);
+
+    // removing twice, then again once the tile is back.
+    ask_edit(&mut app, EventTileRemovalAsked{grid_position: POSITION});
+    ask_edit(&mut app, EventTileRemovalAsked{grid_position: POSITION});
+    assert_eq!(get_tile_id(&app, POSITION), None);
+    assert_grid_matches_entities(&mut app);
+
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_edit(&mut app, EventTileRemovalAsked{grid_position: POSITION});
+    assert_eq!(get_tile_id(&app, POSITION), None);
+    assert_grid_matches_entities(&mut app);
+}
+
+#[test]
+fn hedgehog_is_removed_with_its_floor() {
+    let mut app = build_level_edit_app();
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    assert!(has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+
+    ask_tile_creation(&mut app, TILE_IDX_FIRE, POSITION);
+    assert!(!has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    ask_edit(&mut app, EventTileRemovalAsked{grid_position: POSITION});
+    assert!(!has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    ask_edit(&mut app, EventHedgehogRemovalAsked{grid_position: POSITION});
+    assert!(!has_hedgehog(&app, POSITION));
+    assert_eq!(get_tile_id(&app, POSITION), Some(EnumTilesId::TileIdFloor));
+    assert_grid_matches_entities(&mut app);
+}
+
+#[test]
+fn hedgehog_needs_a_floor() {
+    let mut app = build_level_edit_app();
+    ask_hedgehog_creation(&mut app, POSITION);
+    assert!(!has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+
+    ask_tile_creation(&mut app, TILE_IDX_WALL, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    assert!(!has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+}
+
+#[test]
+fn restored_grid_is_respawned_whole() {
+    let mut app = build_level_edit_app();
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    ask_tile_creation(&mut app, TILE_IDX_WALL, OTHER_POSITION);
+    let level_grid = app.world().resource::<ResCurrentLevelGrid>().clone();
+
+    ask_tile_creation(&mut app, TILE_IDX_FIRE, POSITION);
+    ask_edit(&mut app, EventTileRemovalAsked{grid_position: OTHER_POSITION});
+    app.world_mut().run_system_once(
+        move |mut spawner: LevelSpawner, mut r_grid: ResMut<ResCurrentLevelGrid>| {
+            restore_level_grid(&mut spawner, &mut r_grid, level_grid.clone());
+        }
+    );
+
+    assert_eq!(get_tile_id(&app, POSITION), Some(EnumTilesId::TileIdFloor));
+    assert_eq!(get_tile_id(&app, OTHER_POSITION), Some(EnumTilesId::TileIdWall));
+    assert!(has_hedgehog(&app, POSITION));
+    assert_grid_matches_entities(&mut app);
+}
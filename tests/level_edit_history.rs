@@ -0,0 +1,142 @@
+// Editor edits are recorded by step, cascaded removals with the edit causing
+// them, to be undone and redone as a whole.
+
+mod common;
+
+use common::{
+    ask_edit,
+    ask_hedgehog_creation,
+    ask_tile_creation,
+    build_level_edit_app,
+    get_tile_creation,
+    get_tile_id,
+    has_hedgehog,
+    TILE_IDX_FIRE,
+    TILE_IDX_FLOOR,
+    TILE_IDX_WALL,
+};
+use hedgeclog::level::actions::edit_level_history::{
+    EnumLevelEdit,
+    EventLevelEditRedoAsked,
+    EventLevelEditUndoAsked,
+    ResLevelEditHistory,
+    TileEditState,
+};
+use hedgeclog::level::definition::level_definition::GridPosition;
+use hedgeclog::level::definition::tiles::{EnumTileOrientation, EnumTilesId};
+
+const POSITION: GridPosition = GridPosition{x: 1, z: 2};
+const OTHER_POSITION: GridPosition = GridPosition{x: 3, z: 0};
+
+fn tile(tile_id: EnumTilesId) -> Option<TileEditState> {
+    Some(TileEditState{tile_id, tile_orientation: EnumTileOrientation::Deg0})
+}
+
+#[test]
+fn cascaded_hedgehog_removal_is_undone_with_its_tile() {
+    let mut history = ResLevelEditHistory::default();
+    let fire_over_floor = EnumLevelEdit::Tile{
+        grid_position: POSITION,
+        before: tile(EnumTilesId::TileIdFloor),
+        after: tile(EnumTilesId::TileIdFire),
+    };
+    let hedgehog_burnt = EnumLevelEdit::Hedgehog{grid_position: POSITION, before: true, after: false};
+    history.start_step();
+    history.record_edit(fire_over_floor);
+    history.record_edit(hedgehog_burnt);
+
+    let step = history.pop_undo_step().unwrap();
+    assert_eq!(step.edits, vec![fire_over_floor, hedgehog_burnt]);
+    // hedgehog comes back first, then the floor it stood on.
+    assert_eq!(
+        step.get_inverse().edits,
+        vec![
+            EnumLevelEdit::Hedgehog{grid_position: POSITION, before: false, after: true},
+            EnumLevelEdit::Tile{
+                grid_position: POSITION,
+                before: tile(EnumTilesId::TileIdFire),
+                after: tile(EnumTilesId::TileIdFloor),
+            },
+        ]
+    );
+}
+
+#[test]
+fn edits_changing_nothing_are_not_undone() {
+    let mut history = ResLevelEditHistory::default();
+    history.start_step();
+    history.record_edit(EnumLevelEdit::Hedgehog{grid_position: POSITION, before: false, after: true});
+    history.start_step();
+    history.record_edit(EnumLevelEdit::Tile{grid_position: POSITION, before: None, after: None});
+
+    let step = history.pop_undo_step().unwrap();
+    assert_eq!(step.edits.len(), 1);
+    assert!(history.pop_undo_step().is_none());
+}
+
+#[test]
+fn new_edit_drops_undone_steps() {
+    let mut history = ResLevelEditHistory::default();
+    history.start_step();
+    history.record_edit(EnumLevelEdit::Hedgehog{grid_position: POSITION, before: false, after: true});
+    let step = history.pop_undo_step().unwrap();
+    history.redo_steps.push(step);
+
+    history.start_step();
+    assert_eq!(history.redo_steps.len(), 1);
+    history.record_edit(EnumLevelEdit::Tile{grid_position: POSITION, before: None, after: tile(EnumTilesId::TileIdWall)});
+    assert!(history.redo_steps.is_empty());
+}
+
+#[test]
+fn undo_and_redo_are_played_on_the_level() {
+    let mut app = build_level_edit_app();
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+    ask_tile_creation(&mut app, TILE_IDX_FIRE, POSITION);
+    assert!(!has_hedgehog(&app, POSITION));
+
+    ask_edit(&mut app, EventLevelEditUndoAsked);
+    assert_eq!(get_tile_id(&app, POSITION), Some(EnumTilesId::TileIdFloor));
+    assert!(has_hedgehog(&app, POSITION));
+
+    ask_edit(&mut app, EventLevelEditRedoAsked);
+    assert_eq!(get_tile_id(&app, POSITION), Some(EnumTilesId::TileIdFire));
+    assert!(!has_hedgehog(&app, POSITION));
+
+    // hedgehog asked on a wall is dropped: nothing to undo.
+    ask_tile_creation(&mut app, TILE_IDX_WALL, OTHER_POSITION);
+    let step_count = app.world().resource::<ResLevelEditHistory>().undo_steps.len();
+    ask_hedgehog_creation(&mut app, OTHER_POSITION);
+    assert!(!has_hedgehog(&app, OTHER_POSITION));
+    assert_eq!(app.world().resource::<ResLevelEditHistory>().undo_steps.len(), step_count);
+}
+
+#[test]
+fn cascaded_removal_joins_its_edit_when_edits_share_a_frame() {
+    let mut app = build_level_edit_app();
+    ask_tile_creation(&mut app, TILE_IDX_FLOOR, POSITION);
+    ask_hedgehog_creation(&mut app, POSITION);
+
+    // fire burns the hedgehog, the floor after it is asked in the same frame.
+    app.world_mut().send_event(get_tile_creation(TILE_IDX_FIRE, POSITION));
+    app.world_mut().send_event(get_tile_creation(TILE_IDX_FLOOR, OTHER_POSITION));
+    app.update();
+    app.update();
+
+    let mut history = std::mem::take(app.world_mut().resource_mut::<ResLevelEditHistory>().as_mut());
+    let floor_step = history.pop_undo_step().unwrap();
+    assert_eq!(floor_step.edits.len(), 1);
+    let fire_step = history.pop_undo_step().unwrap();
+    assert_eq!(
+        fire_step.edits,
+        vec![
+            EnumLevelEdit::Tile{
+                grid_position: POSITION,
+                before: tile(EnumTilesId::TileIdFloor),
+                after: tile(EnumTilesId::TileIdFire),
+            },
+            EnumLevelEdit::Hedgehog{grid_position: POSITION, before: true, after: false},
+        ]
+    );
+}
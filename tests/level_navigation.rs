@@ -0,0 +1,47 @@
+// Which level comes next once a level is won through an exit.
+
+use hedgeclog::episode::episode_definition::ResCurEpisodeGrid;
+use hedgeclog::game::navigation::{get_exit_direction, get_level_size_along, get_neighbour_level_uid};
+use hedgeclog::level::definition::level_definition::{GridPosition, ResCurrentLevelGrid};
+use hedgeclog::puzzle::puzzle_state::EnumDirection;
+use uuid::Uuid;
+
+#[test]
+fn exit_direction_follows_level_edge() {
+    let (width, depth) = (4, 6);
+
+    assert_eq!(get_exit_direction(&GridPosition{x: 0, z: 2}, width, depth), Some(EnumDirection::Up));
+    assert_eq!(get_exit_direction(&GridPosition{x: 3, z: 2}, width, depth), Some(EnumDirection::Down));
+    assert_eq!(get_exit_direction(&GridPosition{x: 1, z: 5}, width, depth), Some(EnumDirection::Left));
+    assert_eq!(get_exit_direction(&GridPosition{x: 1, z: 0}, width, depth), Some(EnumDirection::Right));
+    assert_eq!(get_exit_direction(&GridPosition{x: 0, z: 0}, width, depth), Some(EnumDirection::Up));
+    assert_eq!(get_exit_direction(&GridPosition{x: 2, z: 3}, width, depth), None);
+}
+
+#[test]
+fn neighbour_level_is_found_in_episode_grid() {
+    let (origin, down, left) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let mut r_cur_episode_grid = ResCurEpisodeGrid::default();
+    r_cur_episode_grid.episode_grid[0][5] = Some(origin);
+    r_cur_episode_grid.episode_grid[1][5] = Some(down);
+    r_cur_episode_grid.episode_grid[0][6] = Some(left);
+
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, origin, EnumDirection::Down), Some(down));
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, origin, EnumDirection::Left), Some(left));
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, down, EnumDirection::Up), Some(origin));
+    // no level there, or out of the episode grid.
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, origin, EnumDirection::Right), None);
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, origin, EnumDirection::Up), None);
+    // level not in the episode.
+    assert_eq!(get_neighbour_level_uid(&r_cur_episode_grid, Uuid::new_v4(), EnumDirection::Down), None);
+}
+
+#[test]
+fn level_size_along_direction_follows_level_shape() {
+    let r_grid = ResCurrentLevelGrid::new(4, 6);
+
+    assert_eq!(get_level_size_along(&r_grid, EnumDirection::Up), 4);
+    assert_eq!(get_level_size_along(&r_grid, EnumDirection::Down), 4);
+    assert_eq!(get_level_size_along(&r_grid, EnumDirection::Left), 6);
+    assert_eq!(get_level_size_along(&r_grid, EnumDirection::Right), 6);
+}
@@ -0,0 +1,90 @@
+// Level description <-> RON, as written by the editor and read at loading.
+
+use bevy::prelude::*;
+use hedgeclog::level::actions::serialize::{
+    deserialize_level_description,
+    load_level_description_from_file,
+    serialize_level_description,
+};
+use hedgeclog::level::definition::hedgehog::{EnumTooCloseRule, HedgehogType};
+use hedgeclog::level::definition::level_definition::{LevelDescription, PluginLevelDefinition};
+use hedgeclog::level::definition::tiles::{EnumTileOrientation, EnumTilesId};
+use uuid::Uuid;
+
+fn build_type_registry() -> AppTypeRegistry {
+    let mut app = App::new();
+    app.add_plugins(PluginLevelDefinition);
+    app.world().resource::<AppTypeRegistry>().clone()
+}
+
+#[test]
+fn level_description_round_trip() {
+    let type_registry = build_type_registry();
+    let mut level_description = LevelDescription::new(Uuid::new_v4(), 5, 3);
+    level_description.too_close_rule = EnumTooCloseRule::Die;
+    level_description.level_grid[1][2].tile = Some(EnumTilesId::TileIdFloor);
+    level_description.level_grid[1][2].hedgehog = Some(HedgehogType::HedegehogeTypeStandard);
+    level_description.level_grid[3][0].tile = Some(EnumTilesId::TileIdFire);
+    level_description.level_grid[4][1].tile = Some(EnumTilesId::TileIdWallCorner);
+    level_description.level_grid[4][1].orientation = EnumTileOrientation::Deg270;
+
+    let serialized = serialize_level_description(&level_description, &type_registry).unwrap();
+    let deserialized = deserialize_level_description(&serialized, &type_registry).unwrap();
+
+    assert_eq!(deserialized.uuid, level_description.uuid);
+    assert_eq!((deserialized.width, deserialized.depth), (5, 3));
+    assert_eq!(deserialized.too_close_rule, EnumTooCloseRule::Die);
+    assert_eq!(deserialized.level_grid[1][2].tile, Some(EnumTilesId::TileIdFloor));
+    assert!(deserialized.level_grid[1][2].hedgehog.is_some());
+    assert_eq!(deserialized.level_grid[3][0].tile, Some(EnumTilesId::TileIdFire));
+    assert_eq!(deserialized.level_grid[0][0].tile, None);
+    assert_eq!(deserialized.level_grid[4][1].orientation, EnumTileOrientation::Deg270);
+    assert_eq!(deserialized.level_grid[3][0].orientation, EnumTileOrientation::Deg0);
+}
+
+#[test]
+fn test_level_file_is_loaded() {
+    let type_registry = build_type_registry();
+    let level_description =
+        load_level_description_from_file("levels_desc/test_level.ron", &type_registry).unwrap();
+
+    assert_eq!((level_description.width, level_description.depth), (10, 10));
+    assert_eq!(level_description.level_grid[0][0].tile, Some(EnumTilesId::TileIdFloor));
+    assert!(level_description.level_grid[0][0].hedgehog.is_some());
+    assert_eq!(level_description.too_close_rule, EnumTooCloseRule::Stuck);
+    assert_eq!(level_description.level_grid[0][0].orientation, EnumTileOrientation::Deg0);
+}
+
+#[test]
+fn missing_level_file_is_an_error() {
+    let type_registry = build_type_registry();
+    assert!(load_level_description_from_file("levels_desc/missing.ron", &type_registry).is_err());
+}
+
+#[test]
+fn tile_orientation_follows_editor_rotation() {
+    // editor rotates the tile creator by quarter turns, as many times as asked.
+    let mut transform = Transform::default();
+    let expected = [
+        EnumTileOrientation::Deg90,
+        EnumTileOrientation::Deg180,
+        EnumTileOrientation::Deg270,
+        EnumTileOrientation::Deg0,
+        EnumTileOrientation::Deg90,
+    ];
+    for orientation in expected {
+        transform.rotate_local_y(std::f32::consts::FRAC_PI_2);
+        assert_eq!(EnumTileOrientation::from_rotation(transform.rotation), orientation);
+        assert_eq!(EnumTileOrientation::from_rotation(orientation.to_rotation()), orientation);
+    }
+}
+
+#[test]
+fn level_size_not_matching_grid_is_an_error() {
+    let type_registry = build_type_registry();
+    let mut level_description = LevelDescription::new(Uuid::new_v4(), 4, 4);
+    level_description.level_grid[2].pop();
+
+    let serialized = serialize_level_description(&level_description, &type_registry).unwrap();
+    assert!(deserialize_level_description(&serialized, &type_registry).is_err());
+}
@@ -0,0 +1,79 @@
+// Solver finds the shortest winning moves, or tells no move can win, and
+// every shipped level is solvable.
+//
+// Grids are written as in tests/common, one row per x, one char per z.
+
+mod common;
+
+use common::build_state;
+use bevy::prelude::*;
+use hedgeclog::episode::episode_definition::PluginEpsiodeDefinition;
+use hedgeclog::episode::serialize_episode::load_episode_description_from_file;
+use hedgeclog::level::definition::level_definition::PluginLevelDefinition;
+use hedgeclog::puzzle::puzzle_state::{EnumDirection, EnumPuzzleOutcome};
+use hedgeclog::puzzle::solver::{
+    solve,
+    solve_level_description,
+    EnumSolverResult,
+    SolverBounds,
+};
+
+#[test]
+fn shortest_winning_moves_are_found() {
+    // Going up first would burn the hedgehog: way around the obstacle.
+    let state = build_state(&[
+        "E..",
+        "F#.",
+        "h..",
+    ]);
+    let EnumSolverResult::Solved(moves) = solve(&state, &SolverBounds::default()) else {
+        panic!("level should be solvable");
+    };
+    assert_eq!(moves.len(), 6);
+
+    let mut replayed = state.clone();
+    for direction in moves {
+        replayed.step(direction);
+    }
+    assert_eq!(replayed.get_outcome(), EnumPuzzleOutcome::Won);
+}
+
+#[test]
+fn unreachable_exit_has_no_solution() {
+    let state = build_state(&[
+        "h#E",
+    ]);
+    assert_eq!(solve(&state, &SolverBounds::default()), EnumSolverResult::NoSolution);
+}
+
+#[test]
+fn search_stops_on_move_bound() {
+    let state = build_state(&[
+        "h...E",
+    ]);
+    let bounds = SolverBounds{max_move_count: 3, ..default()};
+    assert_eq!(solve(&state, &bounds), EnumSolverResult::NoSolutionWithinBound);
+    assert_eq!(
+        solve(&state, &SolverBounds::default()),
+        EnumSolverResult::Solved(vec![EnumDirection::Left; 4]),
+    );
+}
+
+#[test]
+fn shipped_levels_are_solvable() {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition));
+    let type_registry = app.world().resource::<AppTypeRegistry>().clone();
+
+    let mut level_count = 0;
+    for entry in std::fs::read_dir("assets/episodes").unwrap() {
+        let file_path = entry.unwrap().path().to_string_lossy().to_string();
+        let episode_description = load_episode_description_from_file(&file_path, &type_registry).unwrap();
+        for level_description in episode_description.levels.iter() {
+            let result = solve_level_description(level_description, &SolverBounds::default());
+            assert!(result.is_solved(), "{file_path}: level {} is {result:?}", level_description.uuid);
+            level_count += 1;
+        }
+    }
+    assert!(level_count > 0);
+}
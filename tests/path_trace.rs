@@ -0,0 +1,56 @@
+// Cells stepped on by hedgehogs while playing moves, water slides included.
+//
+// Grids are written as in tests/common, one row per x, one char per z.
+
+mod common;
+
+use common::build_state;
+use hedgeclog::level::definition::level_definition::GridPosition;
+use hedgeclog::puzzle::path_trace::PathTrace;
+use hedgeclog::puzzle::puzzle_state::EnumDirection;
+use hedgeclog::puzzle::solver::{solve, EnumSolverResult, SolverBounds};
+
+fn step_counts(trace: &PathTrace, rows: usize, depth: usize) -> Vec<Vec<usize>> {
+    (0..rows)
+        .map(|x| (0..depth).map(|z| trace.get_step_count(&GridPosition{x, z})).collect())
+        .collect()
+}
+
+#[test]
+fn slid_over_water_cells_are_stepped_on() {
+    let state = build_state(&[
+        "hWW.E",
+        ".....",
+    ]);
+    let trace = PathTrace::from_moves(&state, &[EnumDirection::Left, EnumDirection::Left]);
+    assert_eq!(step_counts(&trace, 2, 5), vec![
+        vec![1, 1, 1, 1, 1],
+        vec![0, 0, 0, 0, 0],
+    ]);
+}
+
+#[test]
+fn blocked_moves_step_on_nothing() {
+    let state = build_state(&[
+        "h#E",
+    ]);
+    let trace = PathTrace::from_moves(&state, &[EnumDirection::Left, EnumDirection::Up]);
+    assert_eq!(step_counts(&trace, 1, 3), vec![vec![1, 0, 0]]);
+}
+
+#[test]
+fn cells_off_the_solution_are_unused() {
+    let state = build_state(&[
+        "h.E",
+        "...",
+    ]);
+    let EnumSolverResult::Solved(moves) = solve(&state, &SolverBounds::default()) else {
+        panic!("level should be solvable");
+    };
+    let trace = PathTrace::from_moves(&state, &moves);
+    assert_eq!(trace.get_max_step_count(), 1);
+    assert_eq!(step_counts(&trace, 2, 3), vec![
+        vec![1, 1, 1],
+        vec![0, 0, 0],
+    ]);
+}
@@ -0,0 +1,121 @@
+// Gameplay rules played on `PuzzleState`, without any bevy app.
+//
+// Grids are written as in tests/common, one row per x, one char per z.
+
+mod common;
+
+use common::build_state;
+use hedgeclog::level::definition::hedgehog::EnumHedgehogOnGrid;
+use hedgeclog::level::definition::level_definition::GridPosition;
+use hedgeclog::puzzle::puzzle_state::{
+    EnumDirection,
+    EnumPuzzleOutcome,
+    EnumStepEvent,
+    PuzzleState,
+};
+
+fn hedgehog_at(state: &PuzzleState, x: usize, z: usize) -> EnumHedgehogOnGrid {
+    state.get_hedgehog(&GridPosition{x, z})
+}
+
+#[test]
+fn hedgehog_moves_on_floor_and_stops_on_obstacle_edge_and_void() {
+    let mut state = build_state(&["h.#", "h  ", ".h."]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 1), EnumHedgehogOnGrid::HedgehogAlive); // obstacle after.
+    assert_eq!(hedgehog_at(&state, 1, 0), EnumHedgehogOnGrid::HedgehogAlive); // void.
+    assert_eq!(hedgehog_at(&state, 2, 2), EnumHedgehogOnGrid::HedgehogAlive);
+
+    let report = state.step(EnumDirection::Left);
+    assert!(report.is_empty()); // edge of the grid, or blocked.
+}
+
+#[test]
+fn line_of_hedgehogs_moves_as_a_whole() {
+    let mut state = build_state(&["hh."]);
+    let report = state.step(EnumDirection::Left);
+
+    assert_eq!(
+        report.events,
+        vec![
+            EnumStepEvent::Moved{from: GridPosition{x: 0, z: 1}, to: GridPosition{x: 0, z: 2}},
+            EnumStepEvent::Moved{from: GridPosition{x: 0, z: 0}, to: GridPosition{x: 0, z: 1}},
+        ]
+    );
+}
+
+#[test]
+fn fire_burns_hedgehog_and_loses_level() {
+    let mut state = build_state(&["hF."]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 1), EnumHedgehogOnGrid::HedgehogDeadBurnt);
+    assert_eq!(state.get_outcome(), EnumPuzzleOutcome::Lost);
+
+    // dead hedgehog does not move anymore.
+    let report = state.step(EnumDirection::Left);
+    assert!(report.is_empty());
+}
+
+#[test]
+fn water_slides_hedgehog_until_it_leaves_water() {
+    let mut state = build_state(&["hWWW.."]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 4), EnumHedgehogOnGrid::HedgehogAlive);
+}
+
+#[test]
+fn water_slide_stops_on_obstacle_hedgehog_and_edge() {
+    let mut state = build_state(&["hWW#", "hWWh", "hWW"]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 2), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&state, 1, 2), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&state, 2, 2), EnumHedgehogOnGrid::HedgehogAlive);
+}
+
+#[test]
+fn water_chain_of_hedgehogs_piles_up_in_order() {
+    let mut state = build_state(&["hwWW#"]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 3), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&state, 0, 2), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&state, 0, 0), EnumHedgehogOnGrid::Empty);
+    assert_eq!(hedgehog_at(&state, 0, 1), EnumHedgehogOnGrid::Empty);
+}
+
+#[test]
+fn water_slide_ending_on_fire_burns_hedgehog() {
+    let mut state = build_state(&["hWWF."]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 3), EnumHedgehogOnGrid::HedgehogDeadBurnt);
+    assert_eq!(hedgehog_at(&state, 0, 4), EnumHedgehogOnGrid::Empty);
+}
+
+#[test]
+fn water_slide_ending_on_exit_rescues_hedgehog_and_wins() {
+    let mut state = build_state(&["hWWE."]);
+    let report = state.step(EnumDirection::Left);
+
+    assert_eq!(hedgehog_at(&state, 0, 3), EnumHedgehogOnGrid::Empty);
+    assert_eq!(state.hedgehog_saved_number, 1);
+    assert_eq!(report.events.last(), Some(&EnumStepEvent::Rescued{position: GridPosition{x: 0, z: 3}}));
+    assert_eq!(state.get_outcome(), EnumPuzzleOutcome::Won);
+}
+
+#[test]
+fn exit_frees_its_cell_for_next_hedgehogs() {
+    let mut state = build_state(&["hhE"]);
+    state.step(EnumDirection::Left);
+
+    assert_eq!(state.hedgehog_saved_number, 1);
+    assert_eq!(state.get_outcome(), EnumPuzzleOutcome::Playing);
+
+    state.step(EnumDirection::Left);
+    assert_eq!(state.hedgehog_saved_number, 2);
+    assert_eq!(state.get_outcome(), EnumPuzzleOutcome::Won);
+}
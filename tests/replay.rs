@@ -0,0 +1,129 @@
+// Replays keep every input of a playthrough: read back, they still reach
+// the recorded outcome unless the level or the rules changed.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use common::build_grid;
+use hedgeclog::app::builder_bevy_app::get_headless_type_registry;
+use hedgeclog::asset::asset_loader::HedgehogAssets;
+use hedgeclog::config::{PluginConfig, StateLevelLoaded, StateUserInputAllowed};
+use hedgeclog::episode::episode_definition::{ResCurEpisode, ResCurEpisodeGrid};
+use hedgeclog::game::game::PluginGame;
+use hedgeclog::game::turn::get_level_outcome;
+use hedgeclog::game::replay::{
+    deserialize_replay,
+    play_replay,
+    serialize_replay,
+    verify_replay,
+    EnumReplayInput,
+    EnumReplayOutcome,
+    Replay,
+    ResReplayRecording,
+    REPLAY_VERSION,
+};
+use hedgeclog::level::actions::ascii_level::level_description_from_ascii;
+use hedgeclog::level::definition::camera::EventCameraTransition;
+use hedgeclog::level::definition::hedgehog::ResHedgeHogInfo;
+use hedgeclog::level::definition::level_definition::{LevelDescription, ResCurrentLevel, ResCurrentLevelGrid};
+use hedgeclog::level::definition::tiles::ResCollectionTile;
+use hedgeclog::level::level::ResLevelToLoad;
+use hedgeclog::puzzle::solver::{solve_level_description, EnumSolverResult, SolverBounds};
+
+// Going up first burns the hedgehog.
+const LEVEL_ASCII: &str = "E..\nF#.\nh..\n";
+
+fn build_won_replay(level_description: &LevelDescription) -> Replay {
+    let EnumSolverResult::Solved(moves) = solve_level_description(level_description, &SolverBounds::default()) else {
+        panic!("level should be solvable");
+    };
+    let mut replay = Replay::new(level_description.uuid);
+    replay.inputs = moves.into_iter().map(EnumReplayInput::from_direction).collect();
+    replay.outcome = EnumReplayOutcome::Won;
+    replay
+}
+
+#[test]
+fn replay_round_trip() {
+    let type_registry = get_headless_type_registry();
+    let level_description = level_description_from_ascii(LEVEL_ASCII).unwrap();
+    let mut replay = build_won_replay(&level_description);
+    replay.inputs.push(EnumReplayInput::Undo);
+    replay.inputs.push(EnumReplayInput::Restart);
+
+    let serialized = serialize_replay(&replay, &type_registry).unwrap();
+    let deserialized = deserialize_replay(&serialized, &type_registry).unwrap();
+
+    assert_eq!(deserialized, replay);
+    assert_eq!(deserialized.version, REPLAY_VERSION);
+}
+
+#[test]
+fn undo_and_restart_are_played_as_in_game() {
+    let level_description = level_description_from_ascii(LEVEL_ASCII).unwrap();
+    let mut replay = build_won_replay(&level_description);
+    assert_eq!(verify_replay(&replay, &level_description), Ok(()));
+
+    // lost, then undone: the level is won all the same.
+    let mut inputs = vec![EnumReplayInput::Up, EnumReplayInput::Undo];
+    inputs.extend(replay.inputs.iter().copied());
+    replay.inputs = inputs;
+    assert_eq!(verify_replay(&replay, &level_description), Ok(()));
+
+    // moves once the level is won are ignored, restart is not.
+    replay.inputs.push(EnumReplayInput::Up);
+    assert_eq!(play_replay(&replay, &level_description), EnumReplayOutcome::Won);
+    replay.inputs.push(EnumReplayInput::Restart);
+    assert_eq!(play_replay(&replay, &level_description), EnumReplayOutcome::Playing);
+}
+
+#[test]
+fn changed_level_breaks_replay() {
+    let level_description = level_description_from_ascii(LEVEL_ASCII).unwrap();
+    let replay = build_won_replay(&level_description);
+
+    // exit walled off: same moves no longer win.
+    let mut changed_level_description = level_description.clone();
+    changed_level_description.level_grid[0][0] = changed_level_description.level_grid[1][1];
+    assert!(verify_replay(&replay, &changed_level_description).is_err());
+
+    let other_level_description = level_description_from_ascii("h.E\n").unwrap();
+    assert!(verify_replay(&replay, &other_level_description).is_err());
+}
+
+// Up reaches the exit, undo brings the hedgehog back: played the other way
+// round, the level would be won.
+#[test]
+fn inputs_of_one_frame_are_recorded_as_resolved() {
+    let level_description = level_description_from_ascii("E\nh\n").unwrap();
+    let mut app = App::new();
+    app
+        .add_plugins((MinimalPlugins, StatesPlugin, PluginConfig, PluginGame))
+        .add_event::<EventCameraTransition>()
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<HedgehogAssets>()
+        .init_resource::<ResHedgeHogInfo>()
+        .init_resource::<ResCollectionTile>()
+        .init_resource::<Assets<Mesh>>()
+        .init_resource::<Assets<StandardMaterial>>()
+        .init_resource::<ResCurEpisode>()
+        .init_resource::<ResCurEpisodeGrid>()
+        .init_resource::<ResLevelToLoad>()
+        .insert_resource(ResCurrentLevel{level_uid: Some(level_description.uuid), ..default()})
+        .insert_resource(build_grid(&["E", "h"]));
+    app.world_mut().resource_mut::<NextState<StateLevelLoaded>>().set(StateLevelLoaded::Loaded);
+    app.world_mut().resource_mut::<NextState<StateUserInputAllowed>>().set(StateUserInputAllowed::Allowed);
+    app.update();
+
+    let mut r_keyboard_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    r_keyboard_input.press(KeyCode::ArrowUp);
+    r_keyboard_input.press(KeyCode::KeyU);
+    app.update();
+
+    let replay = app.world().resource::<ResReplayRecording>().replay.clone().unwrap();
+    assert_eq!(replay.inputs, vec![EnumReplayInput::Up, EnumReplayInput::Undo]);
+    let outcome = EnumReplayOutcome::from(get_level_outcome(app.world().resource::<ResCurrentLevelGrid>()));
+    assert_eq!(outcome, EnumReplayOutcome::Playing);
+    assert_eq!(play_replay(&replay, &level_description), outcome);
+}
@@ -0,0 +1,89 @@
+// Save game records player progress, and is migrated rather than wiped when
+// its format changes.
+
+use bevy::prelude::*;
+use hedgeclog::suite::save_game::{
+    deserialize_save_game,
+    migrate_save_game,
+    serialize_save_game,
+    PluginSaveGame,
+    SaveGame,
+    SAVE_GAME_VERSION,
+};
+use hedgeclog::suite::suite_definition::{
+    EnumEpisodeUnlockRule,
+    PluginSuiteDefinition,
+    SuiteEpisode,
+    SuiteEpisodes,
+};
+use uuid::Uuid;
+
+fn get_type_registry() -> AppTypeRegistry {
+    let mut app = App::new();
+    app.add_plugins((PluginSuiteDefinition, PluginSaveGame));
+    app.world().resource::<AppTypeRegistry>().clone()
+}
+
+#[test]
+fn level_won_keeps_best_move_count() {
+    let mut save_game = SaveGame::default();
+    let level_uid = Uuid::new_v4();
+    assert!(!save_game.is_level_completed(level_uid));
+
+    save_game.record_level_won(level_uid, 12);
+    save_game.record_level_won(level_uid, 8);
+    save_game.record_level_won(level_uid, 10);
+
+    assert!(save_game.is_level_completed(level_uid));
+    assert_eq!(save_game.get_best_move_count(level_uid), Some(8));
+    assert_eq!(save_game.completed_levels.len(), 1);
+}
+
+#[test]
+fn completed_episode_unlocks_the_next_one() {
+    let episode_uids = [Uuid::new_v4(), Uuid::new_v4()];
+    let suite_episodes = SuiteEpisodes {
+        episodes: vec![
+            SuiteEpisode {
+                episode_uid: episode_uids[0],
+                episode_filename: String::new(),
+                unlock_rule: EnumEpisodeUnlockRule::Unlocked,
+            },
+            SuiteEpisode {
+                episode_uid: episode_uids[1],
+                episode_filename: String::new(),
+                unlock_rule: EnumEpisodeUnlockRule::AfterPreviousEpisode,
+            },
+        ]
+    };
+    let mut save_game = SaveGame::default();
+    save_game.record_unlocked_episodes(&suite_episodes);
+    assert_eq!(save_game.unlocked_episode_uids, vec![episode_uids[0]]);
+
+    save_game.record_episode_completed(episode_uids[0]);
+    save_game.record_unlocked_episodes(&suite_episodes);
+    assert_eq!(save_game.unlocked_episode_uids, episode_uids.to_vec());
+}
+
+#[test]
+fn save_game_round_trip() {
+    let type_registry = get_type_registry();
+    let mut save_game = SaveGame::default();
+    save_game.record_level_won(Uuid::new_v4(), 5);
+    save_game.record_episode_completed(Uuid::new_v4());
+
+    let serialized = serialize_save_game(&save_game, &type_registry).unwrap();
+    let deserialized = deserialize_save_game(&serialized, &type_registry).unwrap();
+
+    assert_eq!(deserialized, save_game);
+    assert_eq!(deserialized.version, SAVE_GAME_VERSION);
+}
+
+#[test]
+fn newer_save_game_is_refused() {
+    let save_game = SaveGame {
+        version: SAVE_GAME_VERSION + 1,
+        ..Default::default()
+    };
+    assert!(migrate_save_game(save_game).is_err());
+}
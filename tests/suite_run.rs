@@ -0,0 +1,70 @@
+// Suites order episodes and unlock them as they get completed.
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use hedgeclog::app::common::{ResEpisodeLocation, EPISODE_LOCATION_GAME};
+use hedgeclog::episode::episode_definition::{PluginEpsiodeDefinition, ResCurEpisode};
+use hedgeclog::episode::load_run_episode::s_run_only_episode;
+use hedgeclog::level::definition::level_definition::PluginLevelDefinition;
+use hedgeclog::suite::load_run_suite::{s_load_only_suite, s_run_only_suite};
+use hedgeclog::suite::suite_definition::{
+    get_suite_file_path,
+    EnumEpisodeUnlockRule,
+    PluginSuiteDefinition,
+    ResCurSuite,
+    ResSuiteToRun,
+    SuiteEpisode,
+    SuiteEpisodes,
+    SUITE_DEFAULT_FILENAME,
+};
+use uuid::Uuid;
+
+fn suite_episode(unlock_rule: EnumEpisodeUnlockRule) -> SuiteEpisode {
+    SuiteEpisode {
+        episode_uid: Uuid::new_v4(),
+        episode_filename: String::new(),
+        unlock_rule,
+    }
+}
+
+#[test]
+fn episodes_unlock_as_previous_ones_are_completed() {
+    let first = suite_episode(EnumEpisodeUnlockRule::Unlocked);
+    let second = suite_episode(EnumEpisodeUnlockRule::AfterPreviousEpisode);
+    let third = suite_episode(EnumEpisodeUnlockRule::AfterEpisodes(vec![first.episode_uid]));
+    let (first_uid, second_uid) = (first.episode_uid, second.episode_uid);
+    let suite_episodes = SuiteEpisodes{episodes: vec![first, second, third]};
+
+    let mut completed = HashSet::new();
+    assert!(!suite_episodes.is_episode_unlocked(1, &completed));
+    assert!(!suite_episodes.is_episode_unlocked(2, &completed));
+    assert_eq!(suite_episodes.get_next_episode_idx(&completed), Some(0));
+
+    completed.insert(first_uid);
+    assert!(suite_episodes.is_episode_unlocked(1, &completed));
+    assert!(suite_episodes.is_episode_unlocked(2, &completed));
+    assert_eq!(suite_episodes.get_next_episode_idx(&completed), Some(1));
+
+    completed.insert(second_uid);
+    assert_eq!(suite_episodes.get_next_episode_idx(&completed), Some(2));
+}
+
+#[test]
+fn default_suite_runs_its_first_episode() {
+    let mut app = App::new();
+    app.add_plugins((PluginLevelDefinition, PluginEpsiodeDefinition, PluginSuiteDefinition));
+    app.insert_resource(ResEpisodeLocation{episode_location: EPISODE_LOCATION_GAME.to_string()});
+    app.insert_resource(ResSuiteToRun{suite_path: Some(get_suite_file_path(SUITE_DEFAULT_FILENAME))});
+    let world = app.world_mut();
+
+    world.run_system_once(s_load_only_suite);
+    world.run_system_once(s_run_only_suite);
+    world.run_system_once(s_run_only_episode);
+
+    assert_eq!(world.resource::<ResCurSuite>().current_episode_idx, Some(0));
+    let r_cur_episode = world.resource::<ResCurEpisode>();
+    assert!(r_cur_episode.episode_uid.is_some());
+    assert!(!r_cur_episode.level_entities.is_empty());
+    assert!(r_cur_episode.missing_level_uids.is_empty());
+}
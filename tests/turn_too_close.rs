@@ -0,0 +1,112 @@
+// "Too close" rule, resolved by `resolve_turn` on `ResCurrentLevelGrid`.
+//
+// Grids are written as in tests/common, one row per x, one char per z.
+
+mod common;
+
+use common::build_grid;
+use hedgeclog::config::StateLevelOutcome;
+use hedgeclog::game::turn::{resolve_turn, get_level_outcome};
+use hedgeclog::puzzle::puzzle_state::EnumDirection;
+use hedgeclog::level::definition::hedgehog::{EnumHedgehogOnGrid, EnumTooCloseRule};
+use hedgeclog::level::definition::level_definition::ResCurrentLevelGrid;
+
+fn build_too_close_grid(rows: &[&str], too_close_rule: EnumTooCloseRule) -> ResCurrentLevelGrid {
+    let mut grid = build_grid(rows);
+    grid.too_close_rule = too_close_rule;
+    grid
+}
+
+fn hedgehog_at(grid: &ResCurrentLevelGrid, x: usize, z: usize) -> EnumHedgehogOnGrid {
+    grid.hedgehog_grid[x][z].hedgehog_behaviour
+}
+
+#[test]
+fn stuck_rule_blocked_hedgehog_stays_behind() {
+    let mut grid = build_too_close_grid(&["h.h#"], EnumTooCloseRule::Stuck);
+    let report = resolve_turn(&mut grid, EnumDirection::Left);
+
+    assert_eq!(report.moves.len(), 1);
+    assert!(report.deaths.is_empty());
+    assert_eq!(hedgehog_at(&grid, 0, 0), EnumHedgehogOnGrid::Empty);
+    assert_eq!(hedgehog_at(&grid, 0, 1), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&grid, 0, 2), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(get_level_outcome(&grid), StateLevelOutcome::Playing);
+}
+
+#[test]
+fn stuck_rule_is_the_default() {
+    assert_eq!(ResCurrentLevelGrid::default().too_close_rule, EnumTooCloseRule::Stuck);
+}
+
+#[test]
+fn die_rule_blocked_hedgehog_dies_with_blocker() {
+    let mut grid = build_too_close_grid(&["h.h#"], EnumTooCloseRule::Die);
+    let report = resolve_turn(&mut grid, EnumDirection::Left);
+
+    assert_eq!(report.deaths.len(), 2);
+    assert_eq!(hedgehog_at(&grid, 0, 1), EnumHedgehogOnGrid::HedgehogDeadTooClose);
+    assert_eq!(hedgehog_at(&grid, 0, 2), EnumHedgehogOnGrid::HedgehogDeadTooClose);
+    assert_eq!(get_level_outcome(&grid), StateLevelOutcome::Lost);
+}
+
+#[test]
+fn die_rule_hedgehogs_ending_side_by_side_die() {
+    let mut grid = build_too_close_grid(
+        &[
+            "h...",
+            ".h..",
+            ".#..",
+        ],
+        EnumTooCloseRule::Die
+    );
+    resolve_turn(&mut grid, EnumDirection::Down);
+
+    assert_eq!(hedgehog_at(&grid, 1, 0), EnumHedgehogOnGrid::HedgehogDeadTooClose);
+    assert_eq!(hedgehog_at(&grid, 1, 1), EnumHedgehogOnGrid::HedgehogDeadTooClose);
+}
+
+#[test]
+fn die_rule_hedgehogs_one_cell_apart_survive() {
+    let mut grid = build_too_close_grid(
+        &[
+            "h.h.",
+            "....",
+        ],
+        EnumTooCloseRule::Die
+    );
+    let report = resolve_turn(&mut grid, EnumDirection::Down);
+
+    assert!(report.deaths.is_empty());
+    assert_eq!(hedgehog_at(&grid, 1, 0), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&grid, 1, 2), EnumHedgehogOnGrid::HedgehogAlive);
+}
+
+#[test]
+fn die_rule_diagonal_hedgehogs_are_not_too_close() {
+    let mut grid = build_too_close_grid(
+        &[
+            "h...",
+            ".h..",
+        ],
+        EnumTooCloseRule::Die
+    );
+    let report = resolve_turn(&mut grid, EnumDirection::Left);
+
+    assert!(report.deaths.is_empty());
+    assert_eq!(hedgehog_at(&grid, 0, 1), EnumHedgehogOnGrid::HedgehogAlive);
+    assert_eq!(hedgehog_at(&grid, 1, 2), EnumHedgehogOnGrid::HedgehogAlive);
+}
+
+#[test]
+fn die_rule_ignores_rescued_and_dead_hedgehogs() {
+    let mut grid = build_too_close_grid(&["h.hE", "hF.."], EnumTooCloseRule::Die);
+    resolve_turn(&mut grid, EnumDirection::Left);
+
+    // first row: front hedgehog is rescued, the other one is left alone.
+    assert_eq!(grid.hedgehog_saved_number, 1);
+    assert_eq!(hedgehog_at(&grid, 0, 3), EnumHedgehogOnGrid::Empty);
+    assert_eq!(hedgehog_at(&grid, 0, 1), EnumHedgehogOnGrid::HedgehogAlive);
+    // second row: burnt hedgehog is next to the first row, but dead already.
+    assert_eq!(hedgehog_at(&grid, 1, 1), EnumHedgehogOnGrid::HedgehogDeadBurnt);
+}